@@ -1,19 +1,79 @@
 use crate::calendar::Calendar;
+use crate::config::Config;
+use crate::dateparse;
 use crate::editor::Editor;
+use crate::fields::{self, FieldDef, Form};
+use crate::i18n::Locale;
+use crate::interop::ical;
+use crate::links::{self, LinkIndex};
+use crate::mood;
+use crate::on_this_day::{self, Memory};
+use crate::stats::Stats;
 use crate::storage::DiaryStorage;
-use chrono::NaiveDate;
+use crate::tasks::{self, OpenTask};
+use crate::templates::{self, Templates};
+use chrono::{Datelike, Local, NaiveDate, Timelike};
+use std::collections::{BTreeMap, BTreeSet};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Mode {
     Calendar,
+    Week,
+    Agenda,
+    Stats,
+    Tags,
+    Templates,
+    Tasks,
+    Form,
     Editor,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    GoTo,
+    Note,
+}
+
+/// A one-line text input shown in the status bar.
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+}
+
 pub struct App {
     pub mode: Mode,
+    pub previous_mode: Mode,
     pub calendar: Calendar,
     pub editor: Editor,
     pub storage: DiaryStorage,
+    pub config: Config,
+    pub locale: Locale,
+    /// Read-only event summaries from `config.calendar_overlays`, by date.
+    pub overlay: BTreeMap<NaiveDate, Vec<String>>,
+    /// Built once at startup, then kept up to date by [`App::entry_changed`].
+    pub stats: Stats,
+    /// Which entries link to which dates; kept up to date by [`App::entry_changed`].
+    pub links: LinkIndex,
+    /// Shows the "On this day" panel next to the calendar.
+    pub show_on_this_day: bool,
+    /// Shows the backlinks panel next to the calendar.
+    pub show_backlinks: bool,
+    /// Tag chosen in the tag browser; its days are highlighted and `[`/`]` only visit them.
+    pub tag_filter: Option<String>,
+    /// Index of the highlighted row in the tag browser.
+    pub tag_selection: usize,
+    pub templates: Templates,
+    /// Index of the highlighted row in the template picker.
+    pub template_selection: usize,
+    /// Index of the highlighted row in the tasks view.
+    pub task_selection: usize,
+    /// Set after `m`; the next digit key records the selected day's mood.
+    pub awaiting_mood: bool,
+    /// The field form, while it is open.
+    pub form: Option<Form>,
+    /// Index into `config.fields` of the field shown on the calendar.
+    pub field_overlay: Option<usize>,
+    pub prompt: Option<Prompt>,
     pub should_quit: bool,
     pub status_message: String,
 }
@@ -21,45 +81,653 @@ pub struct App {
 impl App {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         let storage = DiaryStorage::load()?;
+        let config = Config::load()?;
+        let locale = Locale::detect(config.locale);
+
+        let mut calendar = Calendar::new();
+        calendar.week_start = config.week_start;
+        calendar.show_week_numbers = config.show_week_numbers;
+
+        let stats = Stats::new(&storage);
+        let links = LinkIndex::new(&storage, config.week_start);
+
+        let mut status_message = locale.text("status.calendar").to_string();
+        let templates = match Config::get_templates_dir()
+            .and_then(|dir| Templates::load(&dir).map_err(|e| format!("{}: {}", dir.display(), e).into()))
+        {
+            Ok(templates) => templates,
+            Err(e) => {
+                status_message = locale.format("status.template_error", &[&e.to_string()]);
+                Templates::default()
+            }
+        };
+        let overlay = match ical::load_overlay(&config.calendar_overlays) {
+            Ok(overlay) => overlay,
+            Err(e) => {
+                status_message = locale.format("status.overlay_error", &[&e.to_string()]);
+                BTreeMap::new()
+            }
+        };
+
         Ok(Self {
             mode: Mode::Calendar,
-            calendar: Calendar::new(),
+            previous_mode: Mode::Calendar,
+            calendar,
             editor: Editor::new(),
             storage,
+            config,
+            locale,
+            overlay,
+            stats,
+            links,
+            show_on_this_day: false,
+            show_backlinks: false,
+            tag_filter: None,
+            tag_selection: 0,
+            templates,
+            template_selection: 0,
+            task_selection: 0,
+            awaiting_mood: false,
+            form: None,
+            field_overlay: None,
+            prompt: None,
             should_quit: false,
-            status_message: String::from("Press 'q' to quit, Enter to edit, Tab to switch mode"),
+            status_message,
         })
     }
 
-    pub fn switch_to_editor(&mut self) {
-        let content = self
+    /// Key help shown in the status bar for the current mode.
+    fn mode_help(&self) -> &'static str {
+        self.locale.text(match self.mode {
+            Mode::Calendar => "status.calendar",
+            Mode::Week => "status.week",
+            Mode::Agenda => "status.agenda",
+            Mode::Stats => "status.stats",
+            Mode::Tags => "status.tags",
+            Mode::Templates => "status.templates",
+            Mode::Tasks => "status.tasks",
+            Mode::Form => "status.form",
+            Mode::Editor => "status.editing",
+        })
+    }
+
+    pub fn open_prompt(&mut self, kind: PromptKind) {
+        self.prompt = Some(Prompt {
+            kind,
+            input: String::new(),
+        });
+    }
+
+    pub fn prompt_insert_char(&mut self, c: char) {
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.input.push(c);
+        }
+    }
+
+    pub fn prompt_delete_char(&mut self) {
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.input.pop();
+        }
+    }
+
+    pub fn cancel_prompt(&mut self) {
+        self.prompt = None;
+    }
+
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+
+        match prompt.kind {
+            PromptKind::GoTo => self.go_to_date(&prompt.input),
+            PromptKind::Note => self.add_note(&prompt.input),
+        }
+    }
+
+    /// Adds a note stamped with the current time to the selected date and saves.
+    fn add_note(&mut self, input: &str) {
+        let text = input.trim();
+        if text.is_empty() {
+            return;
+        }
+        let date = self.calendar.selected_date;
+        let time = Local::now().time().with_second(0).and_then(|time| time.with_nanosecond(0));
+        let Some(time) = time else {
+            return;
+        };
+        self.storage.add_note(date, time, text.to_string());
+        self.entry_changed(date);
+
+        self.status_message = match self.storage.save() {
+            Ok(()) => self.locale.format("status.note_added", &[&time.format("%H:%M").to_string()]),
+            Err(e) => self.locale.format("status.save_error", &[&e.to_string()]),
+        };
+    }
+
+    fn go_to_date(&mut self, input: &str) {
+        let today = Local::now().naive_local().date();
+        match dateparse::parse_date(input, today, self.calendar.week_start) {
+            Some(date) => {
+                self.calendar.select_date(date);
+                self.status_message = self.mode_help().to_string();
+            }
+            None => {
+                self.status_message = self.locale.format("status.goto_invalid", &[input]);
+            }
+        }
+    }
+
+    pub fn switch_to_week(&mut self) {
+        self.mode = Mode::Week;
+        self.status_message = self.locale.text("status.week").to_string();
+    }
+
+    pub fn switch_to_agenda(&mut self) {
+        let selected = self.calendar.selected_date;
+        if !self.has_entry(&selected) {
+            let nearest = self
+                .storage
+                .next_written_date(&selected)
+                .or_else(|| self.storage.prev_written_date(&selected));
+            if let Some(date) = nearest {
+                self.calendar.select_date(date);
+            }
+        }
+
+        self.mode = Mode::Agenda;
+        self.status_message = self.locale.text("status.agenda").to_string();
+    }
+
+    /// Dates the entry jumps are limited to: those of the chosen tag in the calendar and week view.
+    fn tagged_dates(&self) -> Option<&BTreeSet<NaiveDate>> {
+        if !matches!(self.mode, Mode::Calendar | Mode::Week) {
+            return None;
+        }
+        let tag = self.tag_filter.as_ref()?;
+        self.storage.dates_with_tag(tag)
+    }
+
+    pub fn jump_to_next_entry(&mut self) {
+        let selected = self.calendar.selected_date;
+        let next = match self.tagged_dates() {
+            Some(dates) => dates
+                .range((std::ops::Bound::Excluded(selected), std::ops::Bound::Unbounded))
+                .next()
+                .copied(),
+            None => self.storage.next_written_date(&selected),
+        };
+        match next {
+            Some(date) => self.calendar.select_date(date),
+            None => self.status_message = self.locale.text("status.no_later_entry").to_string(),
+        }
+    }
+
+    pub fn jump_to_prev_entry(&mut self) {
+        let selected = self.calendar.selected_date;
+        let prev = match self.tagged_dates() {
+            Some(dates) => dates.range(..selected).next_back().copied(),
+            None => self.storage.prev_written_date(&selected),
+        };
+        match prev {
+            Some(date) => self.calendar.select_date(date),
+            None => self.status_message = self.locale.text("status.no_earlier_entry").to_string(),
+        }
+    }
+
+    /// Jumps to the first unwritten day after the selected date's run of entries.
+    pub fn jump_to_next_gap(&mut self) {
+        if let Some(date) = self.storage.next_missing_date(&self.calendar.selected_date) {
+            self.calendar.select_date(date);
+        }
+    }
+
+    /// Jumps to the first entry of the next month that has any entries.
+    pub fn agenda_next_month(&mut self) {
+        let selected = self.calendar.selected_date;
+        let next_month = if selected.month() == 12 {
+            NaiveDate::from_ymd_opt(selected.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(selected.year(), selected.month() + 1, 1)
+        };
+
+        if let Some(date) = next_month
+            .and_then(|start| start.pred_opt())
+            .and_then(|end| self.storage.next_written_date(&end))
+        {
+            self.calendar.select_date(date);
+        }
+    }
+
+    /// Jumps to the first entry of the previous month that has any entries.
+    pub fn agenda_prev_month(&mut self) {
+        let month_start = self.calendar.selected_date.with_day(1).unwrap();
+        let Some(prev) = self.storage.prev_written_date(&month_start) else {
+            return;
+        };
+
+        let first = prev
+            .with_day(1)
+            .and_then(|start| start.pred_opt())
+            .and_then(|end| self.storage.next_written_date(&end));
+        if let Some(date) = first {
+            self.calendar.select_date(date);
+        }
+    }
+
+    pub fn toggle_on_this_day(&mut self) {
+        self.show_on_this_day = !self.show_on_this_day;
+    }
+
+    pub fn toggle_backlinks(&mut self) {
+        self.show_backlinks = !self.show_backlinks;
+    }
+
+    /// Entries linking to the selected date, newest first, with their linking lines.
+    pub fn backlinks(&self) -> Vec<(NaiveDate, Vec<String>)> {
+        let target = self.calendar.selected_date;
+        let Some(sources) = self.links.backlinks(&target) else {
+            return Vec::new();
+        };
+        sources
+            .iter()
+            .rev()
+            .map(|source| {
+                let content = self.storage.get_entry(source).unwrap_or_default();
+                let lines = links::mentions(&content, *source, target, self.calendar.week_start)
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect();
+                (*source, lines)
+            })
+            .collect()
+    }
+
+    /// Earlier entries related to the selected date, in the order shown in the panel.
+    pub fn memories(&self) -> Vec<Memory> {
+        on_this_day::memories(
+            &self.storage,
+            self.calendar.selected_date,
+            self.config.on_this_day_recent,
+        )
+    }
+
+    /// Selects the `index`-th entry of the "On this day" panel.
+    pub fn jump_to_memory(&mut self, index: usize) {
+        if let Some(memory) = self.memories().get(index) {
+            self.calendar.select_date(memory.date);
+        }
+    }
+
+    /// Tags with their entry counts, most used first.
+    pub fn tag_list(&self) -> Vec<(&str, usize)> {
+        let mut tags: Vec<(&str, usize)> = self
             .storage
-            .get_entry(&self.calendar.selected_date)
-            .unwrap_or_default();
+            .tag_index()
+            .iter()
+            .map(|(tag, dates)| (tag.as_str(), dates.len()))
+            .collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        tags
+    }
+
+    pub fn switch_to_tags(&mut self) {
+        if let Some(tag) = &self.tag_filter {
+            if let Some(index) = self.tag_list().iter().position(|(t, _)| t == tag) {
+                self.tag_selection = index;
+            }
+        }
+        self.tag_selection = self.tag_selection.min(self.tag_list().len().saturating_sub(1));
+        self.mode = Mode::Tags;
+        self.status_message = self.locale.text("status.tags").to_string();
+    }
+
+    pub fn move_tag_selection(&mut self, delta: i32) {
+        let count = self.tag_list().len();
+        if count == 0 {
+            return;
+        }
+        self.tag_selection = (self.tag_selection as i64 + delta as i64).clamp(0, count as i64 - 1) as usize;
+    }
+
+    /// Filters the calendar by the highlighted tag and selects its latest entry.
+    pub fn apply_tag_selection(&mut self) {
+        let Some(tag) = self.tag_list().get(self.tag_selection).map(|(t, _)| t.to_string()) else {
+            return;
+        };
+        if let Some(latest) = self.storage.dates_with_tag(&tag).and_then(|dates| dates.last()) {
+            self.calendar.select_date(*latest);
+        }
+        self.tag_filter = Some(tag);
+        self.switch_to_calendar();
+    }
+
+    pub fn clear_tag_filter(&mut self) {
+        self.tag_filter = None;
+    }
+
+    /// Whether `date` has the tag chosen in the tag browser.
+    pub fn matches_tag_filter(&self, date: &NaiveDate) -> bool {
+        self.tag_filter
+            .as_ref()
+            .and_then(|tag| self.storage.dates_with_tag(tag))
+            .is_some_and(|dates| dates.contains(date))
+    }
+
+    pub fn switch_to_templates(&mut self) {
+        let count = self.templates.names().len();
+        if count == 0 {
+            self.status_message = self.locale.text("status.no_templates").to_string();
+            return;
+        }
+        self.template_selection = self.template_selection.min(count - 1);
+        self.mode = Mode::Templates;
+        self.status_message = self.locale.text("status.templates").to_string();
+    }
+
+    pub fn move_template_selection(&mut self, delta: i32) {
+        let count = self.templates.names().len();
+        if count == 0 {
+            return;
+        }
+        self.template_selection =
+            (self.template_selection as i64 + delta as i64).clamp(0, count as i64 - 1) as usize;
+    }
+
+    /// Opens the editor with the highlighted template, added below the entry if there is one.
+    pub fn apply_template_selection(&mut self) {
+        let Some(template) = self
+            .templates
+            .names()
+            .get(self.template_selection)
+            .and_then(|name| self.templates.get(name))
+        else {
+            return;
+        };
+        let date = self.calendar.selected_date;
+        let (text, cursor) = templates::render(template, date, &self.storage, self.locale);
+
+        let (content, cursor) = match self.storage.get_entry(&date) {
+            Some(existing) => {
+                let prefix = format!("{}\n\n", existing.trim_end());
+                let offset = prefix.chars().count();
+                (prefix + &text, cursor.map(|c| c + offset))
+            }
+            None => (text, cursor),
+        };
+        self.mode = Mode::Calendar;
+        self.open_editor(content, cursor);
+    }
+
+    /// Open tasks across the diary, in the order of the tasks view.
+    pub fn task_list(&self) -> Vec<OpenTask> {
+        tasks::open_tasks(&self.storage)
+    }
+
+    pub fn switch_to_tasks(&mut self) {
+        self.task_selection = self.task_selection.min(self.task_list().len().saturating_sub(1));
+        self.mode = Mode::Tasks;
+        self.status_message = self.locale.text("status.tasks").to_string();
+    }
+
+    pub fn move_task_selection(&mut self, delta: i32) {
+        let count = self.task_list().len();
+        if count == 0 {
+            return;
+        }
+        self.task_selection = (self.task_selection as i64 + delta as i64).clamp(0, count as i64 - 1) as usize;
+    }
+
+    /// Opens the highlighted task's entry with the cursor on its line.
+    pub fn open_task_selection(&mut self) {
+        let Some(task) = self.task_list().into_iter().nth(self.task_selection) else {
+            return;
+        };
+        self.calendar.select_date(task.date);
+        self.switch_to_editor();
+        self.editor.move_cursor_to_line(task.line);
+    }
+
+    pub fn switch_to_stats(&mut self) {
+        self.mode = Mode::Stats;
+        self.status_message = self.locale.text("status.stats").to_string();
+    }
+
+    /// Keeps caches derived from the storage in sync after the entry for `date` changed.
+    fn entry_changed(&mut self, date: NaiveDate) {
+        let content = self.storage.get_entry(&date);
+        self.stats.update(date, content.as_deref());
+        self.links.update(date, content.as_deref());
+        self.stats.update_mood(date, self.storage.mood(&date));
+    }
+
+    pub fn start_mood_input(&mut self) {
+        self.awaiting_mood = true;
+        self.status_message = self
+            .locale
+            .format("status.mood_prompt", &[&mood::MAX.to_string()]);
+    }
+
+    pub fn cancel_mood_input(&mut self) {
+        self.awaiting_mood = false;
+        self.status_message = self.mode_help().to_string();
+    }
+
+    /// Handles the key pressed after `m`: a digit up to `mood::MAX` sets the mood, `0` clears it.
+    pub fn input_mood(&mut self, c: char) {
+        match c.to_digit(10) {
+            Some(0) => self.set_mood(None),
+            Some(digit) if digit <= u32::from(mood::MAX) => self.set_mood(Some(digit as u8)),
+            _ => self.cancel_mood_input(),
+        }
+    }
+
+    /// Records the selected day's mood (`None` clears it) and saves immediately.
+    fn set_mood(&mut self, mood: Option<u8>) {
+        self.awaiting_mood = false;
+        let date = self.calendar.selected_date;
+        self.storage.set_mood(date, mood);
+        self.entry_changed(date);
+
+        self.status_message = match self.storage.save() {
+            Ok(()) => match mood {
+                Some(mood) => self.locale.format("status.mood_saved", &[mood::emoji(mood)]),
+                None => self.locale.text("status.mood_cleared").to_string(),
+            },
+            Err(e) => self.locale.format("status.save_error", &[&e.to_string()]),
+        };
+    }
+
+    /// Opens the field form for the selected date.
+    pub fn open_form(&mut self) {
+        if self.config.fields.is_empty() {
+            self.status_message = self.locale.text("status.no_fields").to_string();
+            return;
+        }
+        let date = self.calendar.selected_date;
+        let stored = self.storage.metadata(&date).map(|metadata| &metadata.fields);
+        self.form = Some(Form::new(date, &self.config.fields, stored));
+        self.mode = Mode::Form;
+        self.status_message = self.locale.text("status.form").to_string();
+    }
+
+    pub fn move_form_selection(&mut self, delta: i32) {
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+        self.status_message = match form.move_selection(&self.config.fields, delta) {
+            Ok(()) => self.locale.text("status.form").to_string(),
+            Err(name) => self.locale.format("status.field_invalid", &[&name]),
+        };
+    }
+
+    pub fn cycle_form_value(&mut self, delta: i32) {
+        if let Some(form) = self.form.as_mut() {
+            form.cycle(&self.config.fields, delta);
+        }
+    }
+
+    pub fn form_insert_char(&mut self, c: char) {
+        if let Some(form) = self.form.as_mut() {
+            form.insert_char(&self.config.fields, c);
+        }
+    }
+
+    pub fn form_delete_char(&mut self) {
+        if let Some(form) = self.form.as_mut() {
+            form.delete_char();
+        }
+    }
+
+    pub fn clear_form_value(&mut self) {
+        if let Some(form) = self.form.as_mut() {
+            form.clear();
+        }
+    }
+
+    /// Stores the form's values and saves; an invalid number keeps the form open.
+    pub fn save_form(&mut self) {
+        let Some(form) = self.form.as_mut() else {
+            return;
+        };
+        let date = form.date;
+        let values = match form.finish(&self.config.fields) {
+            Ok(values) => values,
+            Err(name) => {
+                self.status_message = self.locale.format("status.field_invalid", &[&name]);
+                return;
+            }
+        };
+        self.form = None;
+        for (def, value) in self.config.fields.iter().zip(values) {
+            self.storage.set_field(date, &def.name, value);
+        }
+        self.entry_changed(date);
+
+        self.mode = Mode::Calendar;
+        self.status_message = match self.storage.save() {
+            Ok(()) => self.locale.text("status.fields_saved").to_string(),
+            Err(e) => self.locale.format("status.save_error", &[&e.to_string()]),
+        };
+    }
+
+    pub fn cancel_form(&mut self) {
+        self.form = None;
+        self.mode = Mode::Calendar;
+        self.status_message = self.locale.text("status.cancelled").to_string();
+    }
+
+    /// Cycles the calendar overlay through the configured fields and back to none.
+    pub fn cycle_field_overlay(&mut self) {
+        self.field_overlay = match self.field_overlay {
+            None if !self.config.fields.is_empty() => Some(0),
+            Some(index) if index + 1 < self.config.fields.len() => Some(index + 1),
+            _ => None,
+        };
+        self.status_message = match self.field_overlay_def() {
+            Some(def) => self.locale.format("status.field_overlay", &[&def.name]),
+            None if self.config.fields.is_empty() => self.locale.text("status.no_fields").to_string(),
+            None => self.locale.text("status.field_overlay_off").to_string(),
+        };
+    }
+
+    /// The field shown on the calendar, if any.
+    pub fn field_overlay_def(&self) -> Option<&FieldDef> {
+        self.field_overlay.and_then(|index| self.config.fields.get(index))
+    }
+
+    /// The smallest and largest recorded values of the overlaid number field.
+    pub fn field_overlay_range(&self) -> Option<(f64, f64)> {
+        let def = self.field_overlay_def()?;
+        fields::number_range(self.storage.field_values(&def.name, ..).map(|(_, value)| value))
+    }
+
+    pub fn switch_to_calendar(&mut self) {
+        self.mode = Mode::Calendar;
+        self.status_message = self.locale.text("status.calendar").to_string();
+    }
+
+    /// Opens the selected date's entry, or a new one from its template and
+    /// the open tasks of the previous entry.
+    pub fn switch_to_editor(&mut self) {
+        let date = self.calendar.selected_date;
+        let (mut content, cursor) = match self.storage.get_entry(&date) {
+            Some(content) => (content, None),
+            None => match self.templates.for_date(date) {
+                Some(template) => templates::render(template, date, &self.storage, self.locale),
+                None => (String::new(), None),
+            },
+        };
+        if self.config.carry_over_tasks && !self.storage.has_entry(&date) {
+            if let Some(previous) = self.storage.prev_entry_date(&date).and_then(|d| self.storage.get_entry(&d)) {
+                content = tasks::carry_over(&content, &previous);
+            }
+        }
+        self.open_editor(content, cursor);
+    }
+
+    /// Starts editing `content`, with the cursor at the end unless given.
+    fn open_editor(&mut self, content: String, cursor: Option<usize>) {
         self.editor.set_content(content);
+        if let Some(cursor) = cursor {
+            self.editor.cursor_position = cursor;
+        }
+        self.previous_mode = self.mode;
         self.mode = Mode::Editor;
-        self.status_message = String::from("Editing mode - Tab to save and return, Esc to cancel");
+        self.status_message = self.locale.text("status.editing").to_string();
     }
 
     pub fn save_and_return_to_calendar(&mut self) {
-        self.storage
-            .set_entry(self.calendar.selected_date, self.editor.content.clone());
+        let date = self.calendar.selected_date;
+        self.storage.set_entry(date, self.editor.content.clone());
+        self.entry_changed(date);
 
         if let Err(e) = self.storage.save() {
-            self.status_message = format!("Error saving: {}", e);
+            self.status_message = self.locale.format("status.save_error", &[&e.to_string()]);
         } else {
-            self.status_message = String::from("Saved successfully! Press 'q' to quit");
+            self.status_message = self.locale.text("status.saved").to_string();
         }
 
-        self.mode = Mode::Calendar;
+        self.mode = self.previous_mode;
+    }
+
+    /// Saves the entry being edited and opens the one the link under the cursor points to.
+    pub fn follow_link(&mut self) {
+        let date = self.calendar.selected_date;
+        let target = links::link_at(&self.editor.content, self.editor.cursor_position)
+            .and_then(|target| links::resolve(target, date, self.calendar.week_start));
+        let Some(target) = target else {
+            self.status_message = self.locale.text("status.no_link").to_string();
+            return;
+        };
+
+        self.storage.set_entry(date, self.editor.content.clone());
+        self.entry_changed(date);
+        if let Err(e) = self.storage.save() {
+            self.status_message = self.locale.format("status.save_error", &[&e.to_string()]);
+            return;
+        }
+
+        self.calendar.select_date(target);
+        self.mode = self.previous_mode;
+        self.switch_to_editor();
     }
 
     pub fn cancel_edit(&mut self) {
-        self.mode = Mode::Calendar;
-        self.status_message = String::from("Edit cancelled");
+        self.mode = self.previous_mode;
+        self.status_message = self.locale.text("status.cancelled").to_string();
     }
 
+    /// Whether `date` has entry text or notes.
     pub fn has_entry(&self, date: &NaiveDate) -> bool {
-        self.storage.has_entry(date)
+        self.storage.is_written(date)
+    }
+
+    /// Whether the entry for `date` reaches the configured daily word goal.
+    pub fn goal_met(&self, date: &NaiveDate) -> bool {
+        match (self.config.daily_word_goal, self.stats.words_on(date)) {
+            (Some(goal), Some(words)) => goal > 0 && words >= goal,
+            _ => false,
+        }
     }
 }
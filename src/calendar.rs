@@ -1,9 +1,20 @@
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+
+/// Width of one month cell: seven 3-column day slots plus the borders.
+pub const MONTH_CELL_WIDTH: u16 = 21 + 2;
+/// Extra width taken by the week-number column.
+pub const WEEK_NUMBER_WIDTH: u16 = 3;
+/// Height of one month cell: weekday header and up to six weeks plus the borders.
+pub const MONTH_CELL_HEIGHT: u16 = 7 + 2;
 
 pub struct Calendar {
     pub current_date: NaiveDate,
     pub selected_date: NaiveDate,
     pub display_months: usize,
+    pub grid_rows: usize,
+    pub grid_cols: usize,
+    pub week_start: Weekday,
+    pub show_week_numbers: bool,
 }
 
 impl Calendar {
@@ -13,9 +24,59 @@ impl Calendar {
             current_date: today,
             selected_date: today,
             display_months: 9,
+            grid_rows: 3,
+            grid_cols: 3,
+            week_start: Weekday::Sun,
+            show_week_numbers: false,
+        }
+    }
+
+    pub fn month_cell_width(&self) -> u16 {
+        if self.show_week_numbers {
+            MONTH_CELL_WIDTH + WEEK_NUMBER_WIDTH
+        } else {
+            MONTH_CELL_WIDTH
         }
     }
 
+    /// Returns how many month rows and columns fit into an area of the given size.
+    pub fn grid_for_area(&self, width: u16, height: u16) -> (usize, usize) {
+        let rows = (height / MONTH_CELL_HEIGHT).max(1) as usize;
+        let cols = (width / self.month_cell_width()).max(1) as usize;
+        (rows, cols)
+    }
+
+    /// Returns the weekdays in display order, starting from `week_start`.
+    pub fn weekdays(&self) -> Vec<Weekday> {
+        let mut weekday = self.week_start;
+        let mut weekdays = Vec::with_capacity(7);
+        for _ in 0..7 {
+            weekdays.push(weekday);
+            weekday = weekday.succ();
+        }
+        weekdays
+    }
+
+    /// Returns the ISO-8601 week number of the calendar row starting at `row_start`.
+    ///
+    /// A row that does not start on Monday spans two ISO weeks, so the week
+    /// containing the row's Monday is used.
+    pub fn week_number(&self, row_start: NaiveDate) -> u32 {
+        let to_monday = Weekday::Mon.days_since(self.week_start) as i64;
+        (row_start + chrono::Duration::days(to_monday)).iso_week().week()
+    }
+
+    pub fn set_grid(&mut self, rows: usize, cols: usize) {
+        if rows == self.grid_rows && cols == self.grid_cols {
+            return;
+        }
+
+        self.grid_rows = rows;
+        self.grid_cols = cols;
+        self.display_months = rows * cols;
+        self.adjust_current_month();
+    }
+
     pub fn move_selection(&mut self, days: i64) {
         if let Some(new_date) = self.selected_date.checked_add_signed(chrono::Duration::days(days)) {
             self.selected_date = new_date;
@@ -23,6 +84,11 @@ impl Calendar {
         }
     }
 
+    pub fn select_date(&mut self, date: NaiveDate) {
+        self.selected_date = date;
+        self.adjust_current_month();
+    }
+
     pub fn next_month(&mut self) {
         if let Some(new_date) = self.current_date.with_day(1).and_then(|d| {
             if d.month() == 12 {
@@ -66,7 +132,7 @@ impl Calendar {
 
     pub fn get_month_days(&self, year: i32, month: u32) -> Vec<Option<NaiveDate>> {
         let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-        let first_weekday = first_day.weekday().num_days_from_sunday() as usize;
+        let first_weekday = first_day.weekday().days_since(self.week_start) as usize;
 
         let days_in_month = if month == 12 {
             NaiveDate::from_ymd_opt(year + 1, 1, 1)
@@ -90,6 +156,16 @@ impl Calendar {
         days
     }
 
+    /// Returns the seven days of the week containing the selected date.
+    pub fn get_week_days(&self) -> Vec<NaiveDate> {
+        let offset = self.selected_date.weekday().days_since(self.week_start) as i64;
+        let start = self.selected_date - chrono::Duration::days(offset);
+
+        (0..7)
+            .map(|i| start + chrono::Duration::days(i))
+            .collect()
+    }
+
     pub fn get_display_months(&self) -> Vec<(i32, u32)> {
         let mut months = Vec::new();
         let mut year = self.current_date.year();
@@ -109,3 +185,81 @@ impl Calendar {
         months
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn calendar(week_start: Weekday) -> Calendar {
+        let mut calendar = Calendar::new();
+        calendar.week_start = week_start;
+        calendar
+    }
+
+    #[test]
+    fn grid_fits_whole_month_cells() {
+        let mut calendar = calendar(Weekday::Sun);
+        assert_eq!(calendar.grid_for_area(0, 0), (1, 1));
+        assert_eq!(calendar.grid_for_area(MONTH_CELL_WIDTH * 2 - 1, MONTH_CELL_HEIGHT * 2 - 1), (1, 1));
+        assert_eq!(calendar.grid_for_area(MONTH_CELL_WIDTH * 2, MONTH_CELL_HEIGHT * 2), (2, 2));
+        assert_eq!(calendar.grid_for_area(MONTH_CELL_WIDTH * 4 + 5, MONTH_CELL_HEIGHT * 3), (3, 4));
+
+        calendar.show_week_numbers = true;
+        assert_eq!(calendar.grid_for_area(MONTH_CELL_WIDTH * 2, MONTH_CELL_HEIGHT), (1, 1));
+        assert_eq!(calendar.grid_for_area((MONTH_CELL_WIDTH + WEEK_NUMBER_WIDTH) * 2, MONTH_CELL_HEIGHT), (1, 2));
+    }
+
+    #[test]
+    fn set_grid_keeps_selection_visible() {
+        let mut calendar = calendar(Weekday::Sun);
+        calendar.current_date = date(2024, 1, 1);
+        calendar.selected_date = date(2024, 9, 15);
+
+        calendar.set_grid(3, 3);
+        assert_eq!(calendar.current_date, date(2024, 1, 1));
+
+        calendar.set_grid(2, 2);
+        assert_eq!(calendar.display_months, 4);
+        assert_eq!(calendar.current_date, date(2024, 9, 1));
+        assert_eq!(calendar.get_display_months().last(), Some(&(2024, 12)));
+    }
+
+    #[test]
+    fn week_numbers_across_year_boundary() {
+        let sunday = calendar(Weekday::Sun);
+        assert_eq!(sunday.week_number(date(2024, 12, 22)), 52);
+        assert_eq!(sunday.week_number(date(2024, 12, 29)), 1);
+        assert_eq!(sunday.week_number(date(2020, 12, 27)), 53);
+        assert_eq!(sunday.week_number(date(2021, 1, 3)), 1);
+
+        let monday = calendar(Weekday::Mon);
+        assert_eq!(monday.week_number(date(2024, 12, 23)), 52);
+        assert_eq!(monday.week_number(date(2024, 12, 30)), 1);
+        assert_eq!(monday.week_number(date(2020, 12, 28)), 53);
+        assert_eq!(monday.week_number(date(2021, 1, 4)), 1);
+    }
+
+    #[test]
+    fn month_days_follow_week_start() {
+        let leading = |calendar: &Calendar, year, month| {
+            let days = calendar.get_month_days(year, month);
+            (days.iter().take_while(|day| day.is_none()).count(), days.iter().flatten().count())
+        };
+
+        // 2024-12-01 is a Sunday and 2025-01-01 a Wednesday.
+        let sunday = calendar(Weekday::Sun);
+        assert_eq!(leading(&sunday, 2024, 12), (0, 31));
+        assert_eq!(leading(&sunday, 2025, 1), (3, 31));
+        assert_eq!(leading(&sunday, 2024, 2), (4, 29));
+
+        let monday = calendar(Weekday::Mon);
+        assert_eq!(leading(&monday, 2024, 12), (6, 31));
+        assert_eq!(leading(&monday, 2025, 1), (2, 31));
+        assert_eq!(monday.weekdays().first(), Some(&Weekday::Mon));
+        assert_eq!(monday.get_month_days(2025, 1).last(), Some(&Some(date(2025, 1, 31))));
+    }
+}
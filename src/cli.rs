@@ -0,0 +1,718 @@
+use crate::config::Config;
+use crate::dateparse;
+use crate::export;
+use crate::export::document::{DocumentFormat, DocumentOptions, Filter};
+use crate::i18n::Locale;
+use crate::interop::{self, daily_notes, dayone, ical, jrnl, ImportReport, MergePolicy};
+use crate::storage::{DiaryStorage, Note};
+use crate::text;
+use chrono::{Local, NaiveDate};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::{self, ExitCode};
+
+/// Exit code when the requested entry or search result does not exist.
+const EXIT_NOT_FOUND: u8 = 1;
+/// Exit code for invalid arguments and I/O or storage errors, as used by clap.
+const EXIT_ERROR: u8 = 2;
+
+#[derive(Parser)]
+#[command(name = "diary_tui", version, about = "Terminal diary with a calendar view")]
+pub struct Cli {
+    /// Print machine-readable JSON instead of plain text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Append text to an entry (today's by default)
+    Add {
+        /// Date of the entry, e.g. 2024-03-15 or yesterday
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+        /// Text to append
+        #[arg(required = true)]
+        text: Vec<String>,
+    },
+    /// Append text read from stdin to an entry (today's by default)
+    Append {
+        /// Date of the entry, e.g. 2024-03-15 or yesterday
+        #[arg(long, allow_hyphen_values = true)]
+        date: Option<String>,
+        /// Put a "## HH:MM" heading with the current time before the text
+        #[arg(long)]
+        timestamp: bool,
+    },
+    /// Edit an entry in $VISUAL or $EDITOR (today's by default)
+    Edit {
+        #[arg(allow_hyphen_values = true)]
+        date: Option<String>,
+    },
+    /// Print an entry
+    Cat {
+        #[arg(allow_hyphen_values = true)]
+        date: String,
+    },
+    /// List entries in chronological order
+    List {
+        /// First date to include
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+        /// Last date to include
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+    },
+    /// Search entries for text, ignoring case
+    Search { query: String },
+    /// Delete an entry
+    Rm {
+        #[arg(allow_hyphen_values = true)]
+        date: String,
+    },
+    /// Print the path of the diary file
+    Path,
+    /// Export entries to other formats
+    Export {
+        #[command(subcommand)]
+        format: ExportFormat,
+    },
+    /// Import entries from jrnl, Day One, iCalendar, Obsidian or Logseq
+    Import {
+        format: ImportFormat,
+        /// File to read, or the notes folder for obsidian and logseq
+        path: PathBuf,
+        /// What to do with dates that already have an entry
+        #[arg(long, value_enum, default_value_t = MergePolicy::Skip)]
+        policy: MergePolicy,
+        /// Report what would change without saving
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ExportFormat {
+    /// Render all entries to a static HTML site
+    Html {
+        /// Output directory
+        dir: PathBuf,
+    },
+    /// Concatenate entries into one Markdown document
+    Markdown(DocumentArgs),
+    /// Concatenate entries into one Org document
+    Org(DocumentArgs),
+    /// Write the fields from the config as CSV, one row per day
+    Csv {
+        /// Output file; the CSV is printed when omitted
+        file: Option<PathBuf>,
+        /// First date to include
+        #[arg(long, allow_hyphen_values = true)]
+        from: Option<String>,
+        /// Last date to include
+        #[arg(long, allow_hyphen_values = true)]
+        to: Option<String>,
+    },
+    /// Write all entries as a jrnl plain-text journal
+    Jrnl {
+        /// Output file
+        file: PathBuf,
+    },
+    /// Write all entries in the format of `jrnl --export json`
+    JrnlJson {
+        /// Output file
+        file: PathBuf,
+    },
+    /// Write all entries to an iCalendar (.ics) file as all-day items
+    Ics {
+        /// Output file
+        file: PathBuf,
+        /// Calendar component used for each entry
+        #[arg(long, value_enum, default_value_t = ical::Component::Journal)]
+        component: ical::Component,
+    },
+    /// Write all entries as a Day One JSON export
+    DayOne {
+        /// Output file
+        file: PathBuf,
+    },
+    /// Write one YYYY-MM-DD.md note per entry
+    Obsidian {
+        /// Vault folder for the daily notes
+        dir: PathBuf,
+        /// What to do with notes that already exist in the folder
+        #[arg(long, value_enum, default_value_t = MergePolicy::Skip)]
+        policy: MergePolicy,
+    },
+    /// Write one journals/YYYY_MM_DD.md page per entry
+    Logseq {
+        /// Logseq graph folder
+        dir: PathBuf,
+        /// What to do with pages that already exist in the graph
+        #[arg(long, value_enum, default_value_t = MergePolicy::Skip)]
+        policy: MergePolicy,
+    },
+}
+
+#[derive(Args)]
+pub struct DocumentArgs {
+    /// Output file; the document is printed when omitted
+    file: Option<PathBuf>,
+    /// First date to include
+    #[arg(long, allow_hyphen_values = true)]
+    from: Option<String>,
+    /// Last date to include
+    #[arg(long, allow_hyphen_values = true)]
+    to: Option<String>,
+    /// Only entries with this tag; repeat to require several
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// Only entries containing this text, ignoring case
+    #[arg(long)]
+    contains: Option<String>,
+    /// Add the weekday to each date heading
+    #[arg(long)]
+    weekday: bool,
+    /// List each entry's tags under its date heading
+    #[arg(long)]
+    show_tags: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ImportFormat {
+    /// jrnl plain-text journal
+    Jrnl,
+    /// Output of `jrnl --export json`
+    JrnlJson,
+    /// Day One JSON export (Journal.json from the exported zip)
+    DayOne,
+    /// iCalendar file; events and journal entries are imported on their start dates
+    Ics,
+    /// Folder of YYYY-MM-DD.md daily notes
+    Obsidian,
+    /// Logseq graph folder with journals/YYYY_MM_DD.md pages
+    Logseq,
+}
+
+enum Outcome {
+    Done,
+    NotFound,
+}
+
+#[derive(Serialize)]
+struct EntryOutput<'a> {
+    date: NaiveDate,
+    content: &'a str,
+    words: usize,
+    #[serde(skip_serializing_if = "<[Note]>::is_empty")]
+    notes: &'a [Note],
+}
+
+#[derive(Serialize)]
+struct ListItem<'a> {
+    date: NaiveDate,
+    weekday: String,
+    title: &'a str,
+    words: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    notes: usize,
+}
+
+#[derive(Serialize)]
+struct SearchResult {
+    date: NaiveDate,
+    /// Matching lines of the entry, then matching notes as `HH:MM text`.
+    lines: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct RemoveOutput {
+    date: NaiveDate,
+    removed: bool,
+}
+
+#[derive(Serialize)]
+struct PathOutput {
+    path: String,
+}
+
+#[derive(Serialize)]
+struct ExportOutput {
+    entries: usize,
+    path: String,
+    /// Dates whose file already existed, for exports into a notes folder.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    collisions: Vec<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy: Option<MergePolicy>,
+}
+
+pub fn run(command: Command, json: bool) -> ExitCode {
+    match execute(command, json) {
+        Ok(Outcome::Done) => ExitCode::SUCCESS,
+        Ok(Outcome::NotFound) => ExitCode::from(EXIT_NOT_FOUND),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn execute(command: Command, json: bool) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+
+    match command {
+        Command::Add { date, text } => add(&config, date.as_deref(), &text.join(" "), json),
+        Command::Append { date, timestamp } => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            if input.trim().is_empty() {
+                return Ok(Outcome::Done);
+            }
+
+            let text = if timestamp {
+                format!("## {}\n\n{}", Local::now().format("%H:%M"), input)
+            } else {
+                input
+            };
+            add(&config, date.as_deref(), &text, json)
+        }
+        Command::Edit { date } => edit(&config, date.as_deref(), json),
+        Command::Cat { date } => cat(&config, &date, json),
+        Command::List { from, to } => list(&config, from.as_deref(), to.as_deref(), json),
+        Command::Search { query } => search(&query, json),
+        Command::Rm { date } => remove(&config, &date, json),
+        Command::Path => path(json),
+        Command::Export { format } => export(&config, format, json),
+        Command::Import {
+            format,
+            path,
+            policy,
+            dry_run,
+        } => import(format, &path, policy, dry_run, json),
+    }
+}
+
+fn add(
+    config: &Config,
+    date: Option<&str>,
+    text: &str,
+    json: bool,
+) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let date = resolve_date(date, config)?;
+    let mut storage = DiaryStorage::load()?;
+    storage.append_entry(date, text);
+    storage.save()?;
+
+    if json {
+        let content = storage.get_entry(&date).unwrap_or_default();
+        print_json(&entry_output(date, &content, storage.notes(&date)))?;
+    }
+    Ok(Outcome::Done)
+}
+
+fn edit(config: &Config, date: Option<&str>, json: bool) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let date = resolve_date(date, config)?;
+    let mut storage = DiaryStorage::load()?;
+    let original = storage.get_entry(&date).unwrap_or_default();
+    let content = edit_in_external_editor(date, &original)?;
+    storage.set_entry(date, content.clone());
+    storage.save()?;
+
+    if json {
+        print_json(&entry_output(date, &content, storage.notes(&date)))?;
+    }
+    Ok(Outcome::Done)
+}
+
+fn cat(config: &Config, date: &str, json: bool) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let date = resolve_date(Some(date), config)?;
+    let storage = DiaryStorage::load()?;
+    let notes = storage.notes(&date);
+    let content = match storage.get_entry(&date) {
+        Some(content) => content,
+        None if !notes.is_empty() => String::new(),
+        None => return Ok(Outcome::NotFound),
+    };
+
+    if json {
+        print_json(&entry_output(date, &content, notes))?;
+    } else {
+        if !content.is_empty() {
+            println!("{}", content.trim_end_matches('\n'));
+        }
+        for note in notes {
+            println!("{}", note);
+        }
+    }
+    Ok(Outcome::Done)
+}
+
+fn list(
+    config: &Config,
+    from: Option<&str>,
+    to: Option<&str>,
+    json: bool,
+) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let from = from.map(|d| resolve_date(Some(d), config)).transpose()?;
+    let to = to.map(|d| resolve_date(Some(d), config)).transpose()?;
+    let storage = DiaryStorage::load()?;
+    let items = list_items(&storage, from.unwrap_or(NaiveDate::MIN)..=to.unwrap_or(NaiveDate::MAX));
+
+    if json {
+        print_json(&items)?;
+    } else {
+        for item in &items {
+            println!("{} {}\t{}", item.date, item.weekday, item.title);
+        }
+    }
+    Ok(Outcome::Done)
+}
+
+/// Days with text or notes in `range`; a day with only notes takes its title from the first note.
+fn list_items(storage: &DiaryStorage, range: RangeInclusive<NaiveDate>) -> Vec<ListItem<'_>> {
+    storage
+        .days_in_range(range)
+        .into_iter()
+        .map(|date| ListItem {
+            date,
+            weekday: date.format("%a").to_string(),
+            title: storage.day_title(&date),
+            words: text::word_count(&storage.get_entry(&date).unwrap_or_default()),
+            notes: storage.notes(&date).len(),
+        })
+        .collect()
+}
+
+fn search_results(storage: &DiaryStorage, query: &str) -> Vec<SearchResult> {
+    let needle = query.to_lowercase();
+    storage
+        .days_in_range(..)
+        .into_iter()
+        .filter_map(|date| {
+            let content = storage.get_entry(&date).unwrap_or_default();
+            let lines: Vec<String> = content
+                .lines()
+                .map(str::to_string)
+                .chain(storage.notes(&date).iter().map(Note::to_string))
+                .filter(|line| line.to_lowercase().contains(&needle))
+                .collect();
+            (!lines.is_empty()).then_some(SearchResult { date, lines })
+        })
+        .collect()
+}
+
+fn search(query: &str, json: bool) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let storage = DiaryStorage::load()?;
+    let results = search_results(&storage, query);
+
+    if json {
+        print_json(&results)?;
+    } else {
+        for result in &results {
+            for line in &result.lines {
+                println!("{}: {}", result.date, line);
+            }
+        }
+    }
+
+    if results.is_empty() {
+        Ok(Outcome::NotFound)
+    } else {
+        Ok(Outcome::Done)
+    }
+}
+
+fn remove(config: &Config, date: &str, json: bool) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let date = resolve_date(Some(date), config)?;
+    let mut storage = DiaryStorage::load()?;
+    let removed = storage.remove_entry(&date);
+    if removed {
+        storage.save()?;
+    }
+
+    if json {
+        print_json(&RemoveOutput { date, removed })?;
+    }
+    if removed {
+        Ok(Outcome::Done)
+    } else {
+        Ok(Outcome::NotFound)
+    }
+}
+
+fn path(json: bool) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let path = DiaryStorage::get_storage_path()?.display().to_string();
+    if json {
+        print_json(&PathOutput { path })?;
+    } else {
+        println!("{}", path);
+    }
+    Ok(Outcome::Done)
+}
+
+fn export(
+    config: &Config,
+    format: ExportFormat,
+    json: bool,
+) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let storage = DiaryStorage::load()?;
+    let locale = Locale::detect(config.locale);
+    let days = storage.days_in_range(..).len();
+    let mut collisions = Vec::new();
+    let mut folder_policy = None;
+
+    let (count, path) = match format {
+        ExportFormat::Html { dir } => {
+            let count = export::html::export(&storage, &dir, locale, config.week_start)?;
+            (count, dir)
+        }
+        ExportFormat::Markdown(args) => {
+            match write_document(config, &storage, DocumentFormat::Markdown, args)? {
+                Some(written) => written,
+                None => return Ok(Outcome::Done),
+            }
+        }
+        ExportFormat::Org(args) => match write_document(config, &storage, DocumentFormat::Org, args)? {
+            Some(written) => written,
+            None => return Ok(Outcome::Done),
+        },
+        ExportFormat::Csv { file, from, to } => {
+            let from = from.as_deref().map(|d| resolve_date(Some(d), config)).transpose()?;
+            let to = to.as_deref().map(|d| resolve_date(Some(d), config)).transpose()?;
+            let (csv, count) = export::csv::render(&storage, &config.fields, from, to);
+            match file {
+                Some(file) => {
+                    fs::write(&file, csv)?;
+                    (count, file)
+                }
+                None => {
+                    print!("{}", csv);
+                    return Ok(Outcome::Done);
+                }
+            }
+        }
+        ExportFormat::Jrnl { file } => {
+            fs::write(&file, jrnl::export_text(&storage))?;
+            (days, file)
+        }
+        ExportFormat::JrnlJson { file } => {
+            fs::write(&file, jrnl::export_json(&storage)?)?;
+            (days, file)
+        }
+        ExportFormat::Ics { file, component } => {
+            fs::write(&file, ical::export(&storage, component))?;
+            (days, file)
+        }
+        ExportFormat::DayOne { file } => {
+            fs::write(&file, dayone::export(&storage)?)?;
+            (days, file)
+        }
+        ExportFormat::Obsidian { dir, policy } => {
+            let report = daily_notes::export(&storage, &dir, daily_notes::Layout::Obsidian, policy)?;
+            collisions = report.collisions;
+            folder_policy = Some(policy);
+            (report.written, dir)
+        }
+        ExportFormat::Logseq { dir, policy } => {
+            let report = daily_notes::export(&storage, &dir, daily_notes::Layout::Logseq, policy)?;
+            collisions = report.collisions;
+            folder_policy = Some(policy);
+            (report.written, dir)
+        }
+    };
+
+    if json {
+        print_json(&ExportOutput {
+            entries: count,
+            path: path.display().to_string(),
+            collisions,
+            policy: folder_policy,
+        })?;
+    } else {
+        if let Some(policy) = folder_policy {
+            for date in &collisions {
+                println!("{} exists ({})", date, policy_name(policy));
+            }
+        }
+        println!("Exported {} entries to {}", count, path.display());
+    }
+    Ok(Outcome::Done)
+}
+
+/// Renders a single-file document and writes it to the requested file,
+/// returning the entry count and path, or prints it when no file was given.
+fn write_document(
+    config: &Config,
+    storage: &DiaryStorage,
+    format: DocumentFormat,
+    args: DocumentArgs,
+) -> Result<Option<(usize, PathBuf)>, Box<dyn std::error::Error>> {
+    let filter = Filter {
+        from: args.from.as_deref().map(|d| resolve_date(Some(d), config)).transpose()?,
+        to: args.to.as_deref().map(|d| resolve_date(Some(d), config)).transpose()?,
+        tags: args.tags,
+        contains: args.contains,
+    };
+    let options = DocumentOptions {
+        format,
+        weekday: args.weekday,
+        tags: args.show_tags,
+    };
+    let (document, count) = export::document::render(storage, &filter, &options, Locale::detect(config.locale));
+
+    match args.file {
+        Some(file) => {
+            fs::write(&file, document)?;
+            Ok(Some((count, file)))
+        }
+        None => {
+            print!("{}", document);
+            Ok(None)
+        }
+    }
+}
+
+fn import(
+    format: ImportFormat,
+    path: &Path,
+    policy: MergePolicy,
+    dry_run: bool,
+    json: bool,
+) -> Result<Outcome, Box<dyn std::error::Error>> {
+    let entries = match format {
+        ImportFormat::Jrnl => jrnl::import_text(&fs::read_to_string(path)?)?,
+        ImportFormat::JrnlJson => jrnl::import_json(&fs::read_to_string(path)?)?,
+        ImportFormat::DayOne => dayone::import(&fs::read_to_string(path)?)?,
+        ImportFormat::Ics => ical::import(&fs::read_to_string(path)?)?,
+        ImportFormat::Obsidian | ImportFormat::Logseq => daily_notes::import(path)?,
+    };
+
+    let mut storage = DiaryStorage::load()?;
+    let report = interop::merge(&mut storage, entries, policy, dry_run);
+    if !dry_run {
+        storage.save()?;
+    }
+
+    if json {
+        print_json(&report)?;
+    } else {
+        print_import_report(&report);
+    }
+    Ok(Outcome::Done)
+}
+
+fn policy_name(policy: MergePolicy) -> &'static str {
+    match policy {
+        MergePolicy::Skip => "skip",
+        MergePolicy::Overwrite => "overwrite",
+        MergePolicy::Append => "append",
+    }
+}
+
+fn print_import_report(report: &ImportReport) {
+    let collision_action = policy_name(report.policy);
+    for date in &report.collisions {
+        println!("{} exists ({})", date, collision_action);
+    }
+
+    let prefix = if report.dry_run { "Dry run: would import" } else { "Imported" };
+    println!(
+        "{} {} new entries; {} dates already had entries ({})",
+        prefix,
+        report.added.len(),
+        report.collisions.len(),
+        collision_action
+    );
+}
+
+/// Parses a date argument with the same rules as the go-to prompt; `None` means today.
+fn resolve_date(
+    input: Option<&str>,
+    config: &Config,
+) -> Result<NaiveDate, Box<dyn std::error::Error>> {
+    let today = Local::now().naive_local().date();
+    match input {
+        None => Ok(today),
+        Some(input) => dateparse::parse_date(input, today, config.week_start)
+            .ok_or_else(|| format!("invalid date: {}", input).into()),
+    }
+}
+
+fn entry_output<'a>(date: NaiveDate, content: &'a str, notes: &'a [Note]) -> EntryOutput<'a> {
+    EntryOutput {
+        date,
+        content,
+        words: text::word_count(content),
+        notes,
+    }
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn std::error::Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Opens `content` in the user's editor and returns the edited text.
+fn edit_in_external_editor(
+    date: NaiveDate,
+    content: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| String::from("vi"));
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("no editor configured")?;
+
+    let path = env::temp_dir().join(format!("diary_tui-{}-{}.md", date, process::id()));
+    fs::write(&path, content)?;
+
+    let status = process::Command::new(program).args(parts).arg(&path).status();
+    let edited = fs::read_to_string(&path);
+    fs::remove_file(&path)?;
+
+    if !status?.success() {
+        return Err(format!("{} exited with an error; entry left unchanged", program).into());
+    }
+    Ok(edited?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    #[test]
+    fn notes_only_days_are_listed_and_searched() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let time = NaiveTime::from_hms_opt(9, 12, 0).unwrap();
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(15), String::from("# Release\nshipped"));
+        storage.add_note(date(15), time, String::from("standup"));
+        storage.add_note(date(16), time, String::from("Standup again"));
+
+        let items = list_items(&storage, date(16)..=date(31));
+        assert_eq!(items.len(), 1);
+        assert_eq!((items[0].date, items[0].title, items[0].words, items[0].notes), (date(16), "Standup again", 0, 1));
+        assert_eq!(list_items(&storage, date(1)..=date(31))[0].title, "Release");
+
+        let results = search_results(&storage, "STANDUP");
+        let found: Vec<(NaiveDate, &str)> = results
+            .iter()
+            .flat_map(|result| result.lines.iter().map(move |line| (result.date, line.as_str())))
+            .collect();
+        assert_eq!(found, vec![(date(15), "09:12 standup"), (date(16), "09:12 Standup again")]);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::fields::FieldDef;
+use crate::i18n::Locale;
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// UI language (`"en"` or `"ja"`); detected from the environment when unset.
+    pub locale: Option<Locale>,
+    /// First day of the week in the calendar and week view, e.g. `"Sun"` or `"Mon"`.
+    pub week_start: Weekday,
+    /// Shows the ISO-8601 week number in front of every calendar row.
+    pub show_week_numbers: bool,
+    /// Shows calendar titles and the editor date in the Japanese era, e.g. `令和8年10月`.
+    pub show_era: bool,
+    /// Annotates days with their 六曜 (先勝, 友引, ...).
+    pub show_rokuyo: bool,
+    /// `.ics` files whose events are shown read-only on the calendar.
+    pub calendar_overlays: Vec<PathBuf>,
+    /// Words to write each day; enables the editor progress gauge and goal marks on the calendar.
+    pub daily_word_goal: Option<usize>,
+    /// Also lists the entries one week and one month before the selected date in the "On this day" panel.
+    pub on_this_day_recent: bool,
+    /// Colors calendar days by their mood.
+    pub mood_colors: bool,
+    /// Copies the open `- [ ]` tasks of the previous entry into a new day's entry.
+    pub carry_over_tasks: bool,
+    /// Typed per-day fields (habits, measurements) edited in the field form.
+    pub fields: Vec<FieldDef>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            locale: None,
+            week_start: Weekday::Sun,
+            show_week_numbers: false,
+            show_era: false,
+            show_rokuyo: false,
+            calendar_overlays: Vec::new(),
+            daily_word_goal: None,
+            on_this_day_recent: false,
+            mood_colors: true,
+            carry_over_tasks: false,
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Self::get_config_path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let config: Config = serde_json::from_str(&content)?;
+        Ok(config)
+    }
+
+    fn get_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let dirs = directories::ProjectDirs::from("", "", "DiaryTui")
+            .ok_or("Failed to get project directory")?;
+        let config_dir = dirs.config_dir();
+        Ok(config_dir.join("config.json"))
+    }
+
+    /// Folder of the entry templates, next to the config file.
+    pub fn get_templates_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let dirs = directories::ProjectDirs::from("", "", "DiaryTui")
+            .ok_or("Failed to get project directory")?;
+        Ok(dirs.config_dir().join("templates"))
+    }
+}
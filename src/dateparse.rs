@@ -0,0 +1,307 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+
+/// Parses a date typed by the user, relative to `today`.
+///
+/// Accepted forms include absolute dates (`2024-03-15`, `2024/3/15`, `3/15`,
+/// `2024-03`, `2024年3月15日`, `3月15日`), keywords (`today`, `yesterday`,
+/// `tomorrow`, `今日`, `昨日`, `明日`, ...), offsets (`-30d`, `+2w`, `-1m`,
+/// `3日前`, `2週間後`, `1ヶ月前`), weekdays (`last friday`, `next mon`,
+/// `this wed`) and Japanese week expressions (`先週の金曜`, `来週月曜日`).
+/// Weeks in the Japanese forms start on `week_start`, like the calendar.
+pub fn parse_date(input: &str, today: NaiveDate, week_start: Weekday) -> Option<NaiveDate> {
+    let input = normalize(input);
+    let input = input.trim();
+
+    if input.is_empty() {
+        return None;
+    }
+
+    parse_keyword(input, today)
+        .or_else(|| parse_absolute(input, today))
+        .or_else(|| parse_offset(input, today))
+        .or_else(|| parse_japanese_offset(input, today))
+        .or_else(|| parse_relative_weekday(input, today))
+        .or_else(|| parse_japanese_week(input, today, week_start))
+}
+
+/// Lowercases the input and folds full-width digits and separators to ASCII.
+fn normalize(input: &str) -> String {
+    input
+        .chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            '／' => '/',
+            '－' => '-',
+            '＋' => '+',
+            '　' => ' ',
+            _ => c,
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn parse_keyword(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let days = match input {
+        "today" | "now" | "今日" | "きょう" | "本日" => 0,
+        "yesterday" | "昨日" | "きのう" => -1,
+        "tomorrow" | "明日" | "あした" | "あす" => 1,
+        "一昨日" | "おととい" => -2,
+        "明後日" | "あさって" => 2,
+        "last week" | "先週" => -7,
+        "next week" | "来週" => 7,
+        "last month" | "先月" => return today.checked_sub_months(Months::new(1)),
+        "next month" | "来月" => return today.checked_add_months(Months::new(1)),
+        "last year" | "去年" | "昨年" => return today.checked_sub_months(Months::new(12)),
+        "next year" | "来年" => return today.checked_add_months(Months::new(12)),
+        _ => return None,
+    };
+    today.checked_add_signed(Duration::days(days))
+}
+
+fn parse_absolute(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    // 2024年3月15日, 2024年3月, 3月15日
+    if input.contains('年') || input.contains('月') {
+        let rest = input.strip_suffix('日').unwrap_or(input);
+        let (year, rest) = match rest.split_once('年') {
+            Some((year, rest)) => (year.parse().ok()?, rest),
+            None => (today.year(), rest),
+        };
+        let (month, day) = match rest.split_once('月') {
+            Some((month, "")) => (month.parse().ok()?, 1),
+            Some((month, day)) => (month.parse().ok()?, day.parse().ok()?),
+            None if rest.is_empty() => (1, 1),
+            None => return None,
+        };
+        return NaiveDate::from_ymd_opt(year, month, day);
+    }
+
+    let parts: Vec<&str> = input.split(['-', '/', '.']).collect();
+    if parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+
+    match parts.as_slice() {
+        [year, month, day] => {
+            NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+        }
+        [year, month] if year.len() == 4 => {
+            NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)
+        }
+        [month, day] => {
+            NaiveDate::from_ymd_opt(today.year(), month.parse().ok()?, day.parse().ok()?)
+        }
+        _ => None,
+    }
+}
+
+/// `-30d`, `+2w`, `-1m`, `+1y`; a missing sign means "from today forward".
+fn parse_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (sign, rest) = match input.chars().next()? {
+        '-' => (-1, &input[1..]),
+        '+' => (1, &input[1..]),
+        _ => (1, input),
+    };
+    let unit = rest.chars().last()?;
+    let amount: i64 = rest[..rest.len() - unit.len_utf8()].parse().ok()?;
+
+    shift(today, amount.checked_mul(sign)?, unit)
+}
+
+/// `3日前`, `2週間後`, `1ヶ月前`, `1年前`
+fn parse_japanese_offset(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (rest, sign) = if let Some(rest) = input.strip_suffix('前') {
+        (rest, -1)
+    } else if let Some(rest) = input.strip_suffix('後') {
+        (rest, 1)
+    } else {
+        return None;
+    };
+
+    let units = [
+        ("週間", 'w'),
+        ("ヶ月", 'm'),
+        ("か月", 'm'),
+        ("カ月", 'm'),
+        ("日", 'd'),
+        ("週", 'w'),
+        ("年", 'y'),
+    ];
+    let (amount, unit) = units.iter().find_map(|(suffix, unit)| {
+        rest.strip_suffix(suffix)
+            .and_then(|amount| amount.parse::<i64>().ok())
+            .map(|amount| (amount, *unit))
+    })?;
+
+    shift(today, amount.checked_mul(sign)?, unit)
+}
+
+fn shift(date: NaiveDate, amount: i64, unit: char) -> Option<NaiveDate> {
+    match unit {
+        'd' => date.checked_add_signed(Duration::try_days(amount)?),
+        'w' => date.checked_add_signed(Duration::try_weeks(amount)?),
+        'm' | 'y' => {
+            let months = if unit == 'y' { amount.checked_mul(12)? } else { amount };
+            let magnitude = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+            if months < 0 {
+                date.checked_sub_months(magnitude)
+            } else {
+                date.checked_add_months(magnitude)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `last friday` (strictly before today), `next friday` (strictly after today),
+/// `this friday` or just `friday` (within the seven days starting today).
+fn parse_relative_weekday(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (direction, name) = match input.split_once(' ') {
+        Some(("last", name)) => (-1, name),
+        Some(("next", name)) => (1, name),
+        Some(("this", name)) => (0, name),
+        None => (0, input),
+        _ => return None,
+    };
+    let weekday: Weekday = name.trim().parse().ok()?;
+
+    let forward = weekday.days_since(today.weekday()) as i64;
+    let days = match direction {
+        -1 => {
+            let back = today.weekday().days_since(weekday) as i64;
+            -(if back == 0 { 7 } else { back })
+        }
+        1 if forward == 0 => 7,
+        _ => forward,
+    };
+    today.checked_add_signed(Duration::days(days))
+}
+
+/// `先週の金曜`, `来週月曜日`, `今週の水曜`: a weekday in the previous, next or current calendar week.
+fn parse_japanese_week(input: &str, today: NaiveDate, week_start: Weekday) -> Option<NaiveDate> {
+    let (weeks, rest) = if let Some(rest) = input.strip_prefix("先々週") {
+        (-2, rest)
+    } else if let Some(rest) = input.strip_prefix("先週") {
+        (-1, rest)
+    } else if let Some(rest) = input.strip_prefix("今週") {
+        (0, rest)
+    } else if let Some(rest) = input.strip_prefix("再来週") {
+        (2, rest)
+    } else if let Some(rest) = input.strip_prefix("来週") {
+        (1, rest)
+    } else if input.contains('曜') {
+        (0, input)
+    } else {
+        // A lone "月" or "日" is too ambiguous to be read as a weekday.
+        return None;
+    };
+
+    let rest = rest.strip_prefix('の').unwrap_or(rest);
+    let rest = rest
+        .strip_suffix("曜日")
+        .or_else(|| rest.strip_suffix('曜'))
+        .unwrap_or(rest);
+
+    let weekday = match rest {
+        "月" => Weekday::Mon,
+        "火" => Weekday::Tue,
+        "水" => Weekday::Wed,
+        "木" => Weekday::Thu,
+        "金" => Weekday::Fri,
+        "土" => Weekday::Sat,
+        "日" => Weekday::Sun,
+        _ => return None,
+    };
+
+    let week_begin = today - Duration::days(today.weekday().days_since(week_start) as i64);
+    let offset = weekday.days_since(week_start) as i64;
+    week_begin.checked_add_signed(Duration::days(weeks * 7 + offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // A Monday.
+    fn today() -> NaiveDate {
+        date(2026, 10, 19)
+    }
+
+    fn parse(input: &str) -> Option<NaiveDate> {
+        parse_date(input, today(), Weekday::Sun)
+    }
+
+    #[test]
+    fn parses_absolute_dates() {
+        assert_eq!(parse("2024-03-15"), Some(date(2024, 3, 15)));
+        assert_eq!(parse("2024/3/5"), Some(date(2024, 3, 5)));
+        assert_eq!(parse("3/15"), Some(date(2026, 3, 15)));
+        assert_eq!(parse("2024-03"), Some(date(2024, 3, 1)));
+        assert_eq!(parse("2024年3月15日"), Some(date(2024, 3, 15)));
+        assert_eq!(parse("3月15日"), Some(date(2026, 3, 15)));
+        assert_eq!(parse("２０２４／３／１５"), Some(date(2024, 3, 15)));
+        assert_eq!(parse("2024-02-30"), None);
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(parse("today"), Some(today()));
+        assert_eq!(parse("Yesterday"), Some(date(2026, 10, 18)));
+        assert_eq!(parse("明日"), Some(date(2026, 10, 20)));
+        assert_eq!(parse("先月"), Some(date(2026, 9, 19)));
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(parse("-30d"), Some(date(2026, 9, 19)));
+        assert_eq!(parse("+2w"), Some(date(2026, 11, 2)));
+        assert_eq!(parse("-1m"), Some(date(2026, 9, 19)));
+        assert_eq!(parse("-2y"), Some(date(2024, 10, 19)));
+        assert_eq!(parse("3日前"), Some(date(2026, 10, 16)));
+        assert_eq!(parse("2週間後"), Some(date(2026, 11, 2)));
+        assert_eq!(parse("1ヶ月前"), Some(date(2026, 9, 19)));
+    }
+
+    #[test]
+    fn overflowing_offsets_are_rejected() {
+        assert_eq!(parse("-200000000000000d"), None);
+        assert_eq!(parse("+9999999999999999w"), None);
+        assert_eq!(parse("-9223372036854775807d"), None);
+        assert_eq!(parse("--9223372036854775808d"), None);
+        assert_eq!(parse("9223372036854775807y"), None);
+        assert_eq!(parse("9999999999999999日前"), None);
+        assert_eq!(parse("9999999999999999週間後"), None);
+    }
+
+    #[test]
+    fn parses_weekdays() {
+        assert_eq!(parse("last friday"), Some(date(2026, 10, 16)));
+        assert_eq!(parse("last monday"), Some(date(2026, 10, 12)));
+        assert_eq!(parse("next monday"), Some(date(2026, 10, 26)));
+        assert_eq!(parse("next wed"), Some(date(2026, 10, 21)));
+        assert_eq!(parse("friday"), Some(date(2026, 10, 23)));
+    }
+
+    #[test]
+    fn parses_japanese_weeks() {
+        assert_eq!(parse("先週の金曜"), Some(date(2026, 10, 16)));
+        assert_eq!(parse("来週月曜日"), Some(date(2026, 10, 26)));
+        assert_eq!(parse("今週の日曜"), Some(date(2026, 10, 18)));
+        assert_eq!(parse("水曜日"), Some(date(2026, 10, 21)));
+        assert_eq!(
+            parse_date("今週の日曜", today(), Weekday::Mon),
+            Some(date(2026, 10, 25))
+        );
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("someday"), None);
+        assert_eq!(parse("12"), None);
+        assert_eq!(parse("月"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::tasks;
+
 pub struct Editor {
     pub content: String,
     pub cursor_position: usize,
@@ -58,13 +60,12 @@ impl Editor {
             let prev_line_len = lines[current_line - 1].chars().count();
             let new_col = col.min(prev_line_len);
 
-            let mut new_pos = 0;
-            for i in 0..(current_line - 1) {
-                new_pos += lines[i].chars().count() + 1;
-            }
-            new_pos += new_col;
+            let new_pos: usize = lines[..current_line - 1]
+                .iter()
+                .map(|line| line.chars().count() + 1)
+                .sum();
 
-            self.cursor_position = new_pos;
+            self.cursor_position = new_pos + new_col;
         }
     }
 
@@ -72,20 +73,47 @@ impl Editor {
         let lines: Vec<&str> = self.content.lines().collect();
         let (current_line, col) = self.get_cursor_line_col();
 
-        if current_line < lines.len() - 1 {
+        if current_line + 1 < lines.len() {
             let next_line_len = lines[current_line + 1].chars().count();
             let new_col = col.min(next_line_len);
 
-            let mut new_pos = 0;
-            for i in 0..=current_line {
-                new_pos += lines[i].chars().count() + 1;
-            }
-            new_pos += new_col;
+            let new_pos: usize = lines[..=current_line]
+                .iter()
+                .map(|line| line.chars().count() + 1)
+                .sum();
 
-            self.cursor_position = new_pos;
+            self.cursor_position = new_pos + new_col;
         }
     }
 
+    /// Puts the cursor at the start of `line`, counting from 0.
+    pub fn move_cursor_to_line(&mut self, line: usize) {
+        self.cursor_position = self
+            .content
+            .split('\n')
+            .take(line)
+            .map(|line| line.chars().count() + 1)
+            .sum::<usize>()
+            .min(self.content.chars().count());
+    }
+
+    /// Checks or unchecks the `- [ ]` item on the cursor line, or turns the line into one.
+    pub fn toggle_checkbox(&mut self) {
+        let byte_pos = self.get_byte_position(self.cursor_position);
+        let line_start = self.content[..byte_pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = self.content[byte_pos..]
+            .find('\n')
+            .map(|i| byte_pos + i)
+            .unwrap_or(self.content.len());
+
+        let line = &self.content[line_start..line_end];
+        let toggled = tasks::toggle(line);
+        // Converting a line inserts text before it, so the cursor keeps its place in the text.
+        let added = toggled.chars().count() - line.chars().count();
+        self.content.replace_range(line_start..line_end, &toggled);
+        self.cursor_position += added;
+    }
+
     fn get_cursor_line_col(&self) -> (usize, usize) {
         let mut char_count = 0;
         let lines: Vec<&str> = self.content.lines().collect();
@@ -0,0 +1,77 @@
+use chrono::{Datelike, NaiveDate};
+
+/// Japanese eras with the Gregorian date of their first day, newest first.
+const ERAS: [(&str, i32, u32, u32); 5] = [
+    ("令和", 2019, 5, 1),
+    ("平成", 1989, 1, 8),
+    ("昭和", 1926, 12, 25),
+    ("大正", 1912, 7, 30),
+    ("明治", 1868, 1, 25),
+];
+
+/// Returns the era name and the year within that era, or `None` before the Meiji era.
+pub fn era_year(date: NaiveDate) -> Option<(&'static str, i32)> {
+    ERAS.iter().find_map(|(name, year, month, day)| {
+        let start = NaiveDate::from_ymd_opt(*year, *month, *day)?;
+        (date >= start).then(|| (*name, date.year() - year + 1))
+    })
+}
+
+/// Formats the year and month in the Japanese era, e.g. `令和8年10月`.
+///
+/// A month in which the era changed is shown in the era of its first day.
+pub fn format_year_month(year: i32, month: u32) -> Option<String> {
+    let first_day = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let (era, era_year) = era_year(first_day)?;
+    Some(format!("{}{}年{}月", era, format_era_year(era_year), month))
+}
+
+/// Formats a date in the Japanese era, e.g. `令和元年5月1日`.
+pub fn format_date(date: NaiveDate) -> Option<String> {
+    let (era, era_year) = era_year(date)?;
+    Some(format!(
+        "{}{}年{}月{}日",
+        era,
+        format_era_year(era_year),
+        date.month(),
+        date.day()
+    ))
+}
+
+/// The first year of an era is written as 元年.
+fn format_era_year(year: i32) -> String {
+    if year == 1 {
+        "元".to_string()
+    } else {
+        year.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn era_boundaries() {
+        assert_eq!(era_year(date(1989, 1, 7)), Some(("昭和", 64)));
+        assert_eq!(era_year(date(1989, 1, 8)), Some(("平成", 1)));
+        assert_eq!(era_year(date(2019, 4, 30)), Some(("平成", 31)));
+        assert_eq!(era_year(date(2019, 5, 1)), Some(("令和", 1)));
+        assert_eq!(era_year(date(1868, 1, 24)), None);
+    }
+
+    #[test]
+    fn first_year_is_gannen() {
+        assert_eq!(format_date(date(2019, 5, 1)).as_deref(), Some("令和元年5月1日"));
+        assert_eq!(format_date(date(1989, 1, 8)).as_deref(), Some("平成元年1月8日"));
+        assert_eq!(format_date(date(2026, 10, 19)).as_deref(), Some("令和8年10月19日"));
+        assert_eq!(format_year_month(2019, 4).as_deref(), Some("平成31年4月"));
+        assert_eq!(format_year_month(2019, 5).as_deref(), Some("令和元年5月"));
+        // The month the era changed in keeps the era of its first day.
+        assert_eq!(format_year_month(1989, 1).as_deref(), Some("昭和64年1月"));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::fields::{FieldDef, FieldValue};
+use crate::storage::DiaryStorage;
+use chrono::NaiveDate;
+
+/// One row per date in the range with a value for any of `fields`, oldest
+/// first, with a `date` column followed by the fields in config order.
+///
+/// Returns the CSV text and the number of rows.
+pub fn render(
+    storage: &DiaryStorage,
+    fields: &[FieldDef],
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> (String, usize) {
+    let range = from.unwrap_or(NaiveDate::MIN)..=to.unwrap_or(NaiveDate::MAX);
+    let header: Vec<String> = std::iter::once(String::from("date"))
+        .chain(fields.iter().map(|def| escape(&def.name)))
+        .collect();
+    let mut csv = header.join(",");
+    csv.push_str("\r\n");
+
+    let mut count = 0;
+    for (date, metadata) in storage.metadata_in_range(range) {
+        let values: Vec<Option<&FieldValue>> = fields.iter().map(|def| metadata.fields.get(&def.name)).collect();
+        if values.iter().all(Option::is_none) {
+            continue;
+        }
+        let row: Vec<String> = std::iter::once(date.format("%Y-%m-%d").to_string())
+            .chain(values.into_iter().map(|value| value.map(cell).unwrap_or_default()))
+            .collect();
+        csv.push_str(&row.join(","));
+        csv.push_str("\r\n");
+        count += 1;
+    }
+    (csv, count)
+}
+
+fn cell(value: &FieldValue) -> String {
+    match value {
+        FieldValue::Bool(b) => b.to_string(),
+        FieldValue::Number(n) => n.to_string(),
+        FieldValue::Text(text) => escape(text),
+    }
+}
+
+/// Quotes a cell as RFC 4180 requires when it contains a delimiter, quote or line break.
+fn escape(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_one_row_per_day_with_fields() {
+        let fields: Vec<FieldDef> = serde_json::from_str(
+            r#"[{"name": "exercise", "type": "bool"}, {"name": "sleep", "type": "number"}, {"name": "note, short", "type": "enum", "options": ["a \"b\""]}]"#,
+        )
+        .unwrap();
+        let date = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let mut storage = DiaryStorage::new();
+        storage.set_field(date(1), "exercise", Some(FieldValue::Bool(true)));
+        storage.set_field(date(2), "sleep", Some(FieldValue::Number(6.5)));
+        storage.set_field(date(2), "note, short", Some(FieldValue::Text(String::from("a \"b\""))));
+        storage.set_mood(date(3), Some(3));
+        storage.set_field(date(4), "sleep", Some(FieldValue::Number(8.0)));
+
+        let (csv, count) = render(&storage, &fields, None, Some(date(3)));
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            csv,
+            "date,exercise,sleep,\"note, short\"\r\n2024-03-01,true,,\r\n2024-03-02,,6.5,\"a \"\"b\"\"\"\r\n"
+        );
+    }
+}
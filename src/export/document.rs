@@ -0,0 +1,307 @@
+use crate::i18n::Locale;
+use crate::storage::DiaryStorage;
+use crate::tags;
+use chrono::{Datelike, NaiveDate};
+
+/// Markdown headings go no deeper than this.
+const MAX_MARKDOWN_LEVEL: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentFormat {
+    Markdown,
+    Org,
+}
+
+/// Which entries go into the document. Empty fields do not filter.
+#[derive(Default)]
+pub struct Filter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// Tags the entry must all have, without `#`.
+    pub tags: Vec<String>,
+    /// Text the entry must contain, ignoring case.
+    pub contains: Option<String>,
+}
+
+impl Filter {
+    fn matches(&self, content: &str) -> bool {
+        let has_tags = self.tags.is_empty() || {
+            let entry_tags = tags::extract_tags(content);
+            self.tags.iter().all(|tag| {
+                entry_tags.contains(&tag.trim_start_matches('#').to_lowercase())
+            })
+        };
+        let has_text = self
+            .contains
+            .as_ref()
+            .is_none_or(|needle| content.to_lowercase().contains(&needle.to_lowercase()));
+        has_tags && has_text
+    }
+}
+
+pub struct DocumentOptions {
+    pub format: DocumentFormat,
+    /// Adds the weekday to each date heading.
+    pub weekday: bool,
+    /// Lists each entry's tags under its heading.
+    pub tags: bool,
+}
+
+/// Concatenates the matching entries into one document, oldest first. Each
+/// entry gets a top-level date heading and its own headings are shifted one
+/// level down; the day's notes follow as a list.
+///
+/// Returns the document and the number of entries in it.
+pub fn render(
+    storage: &DiaryStorage,
+    filter: &Filter,
+    options: &DocumentOptions,
+    locale: Locale,
+) -> (String, usize) {
+    let range = filter.from.unwrap_or(NaiveDate::MIN)..=filter.to.unwrap_or(NaiveDate::MAX);
+    let mut sections = Vec::new();
+
+    for date in storage.days_in_range(range) {
+        let Some(content) = storage.text_with_notes(&date) else {
+            continue;
+        };
+        if !filter.matches(&content) {
+            continue;
+        }
+        let entry_tags = if options.tags {
+            tags::extract_tags(&content)
+        } else {
+            Vec::new()
+        };
+
+        sections.push(match options.format {
+            DocumentFormat::Markdown => markdown_section(date, &content, &entry_tags, options, locale),
+            DocumentFormat::Org => org_section(date, &content, &entry_tags, options),
+        });
+    }
+
+    let count = sections.len();
+    (sections.join("\n"), count)
+}
+
+fn markdown_section(
+    date: NaiveDate,
+    content: &str,
+    entry_tags: &[String],
+    options: &DocumentOptions,
+    locale: Locale,
+) -> String {
+    let mut section = format!("# {}", date.format("%Y-%m-%d"));
+    if options.weekday {
+        section.push_str(&format!(" ({})", locale.weekday_long(date.weekday())));
+    }
+    section.push_str("\n\n");
+
+    if !entry_tags.is_empty() {
+        let line: Vec<String> = entry_tags.iter().map(|tag| format!("`#{}`", tag)).collect();
+        section.push_str(&line.join(" "));
+        section.push_str("\n\n");
+    }
+
+    section.push_str(&shift_markdown_headings(content.trim()));
+    section.push('\n');
+    section
+}
+
+/// Org timestamps use English weekday abbreviations regardless of the UI locale.
+fn org_section(date: NaiveDate, content: &str, entry_tags: &[String], options: &DocumentOptions) -> String {
+    let stamp = if options.weekday {
+        date.format("%Y-%m-%d %a").to_string()
+    } else {
+        date.format("%Y-%m-%d").to_string()
+    };
+    let mut section = format!("* [{}]", stamp);
+
+    if !entry_tags.is_empty() {
+        let line: Vec<String> = entry_tags.iter().map(|tag| org_tag(tag)).collect();
+        section.push_str(&format!(" :{}:", line.join(":")));
+    }
+    section.push('\n');
+
+    section.push_str(&markdown_to_org(content.trim()));
+    section.push('\n');
+    section
+}
+
+/// Moves every ATX heading (`# Title`) one level down, leaving code blocks alone.
+fn shift_markdown_headings(content: &str) -> String {
+    let mut in_fence = false;
+    content
+        .lines()
+        .map(|line| {
+            if is_fence(line) {
+                in_fence = !in_fence;
+                return line.to_string();
+            }
+            match (in_fence, heading_level(line)) {
+                (false, Some((level, text))) => {
+                    format!("{} {}", "#".repeat((level + 1).min(MAX_MARKDOWN_LEVEL)), text)
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Converts the Markdown that matters for structure: headings become org
+/// headlines below the date, `*`/`+` bullets become `-` (a `*` at the start
+/// of a line is a headline in org) and fenced code becomes a source block.
+fn markdown_to_org(content: &str) -> String {
+    let mut in_fence = false;
+    content
+        .lines()
+        .map(|line| {
+            if is_fence(line) {
+                in_fence = !in_fence;
+                if in_fence {
+                    let language = line.trim_start().trim_start_matches(['`', '~']).trim();
+                    return format!("#+BEGIN_SRC {}", language).trim_end().to_string();
+                }
+                return String::from("#+END_SRC");
+            }
+            if in_fence {
+                return line.to_string();
+            }
+            if let Some((level, text)) = heading_level(line) {
+                return format!("{} {}", "*".repeat(level + 1), text);
+            }
+
+            let indent = line.len() - line.trim_start().len();
+            match line.trim_start().strip_prefix("* ").or_else(|| line.trim_start().strip_prefix("+ ")) {
+                Some(item) => format!("{}- {}", &line[..indent], item),
+                None => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The level and text of an ATX heading: up to three spaces, 1-6 `#` and a space.
+fn heading_level(line: &str) -> Option<(usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let level = rest.len() - rest.trim_start_matches('#').len();
+    if level == 0 || level > MAX_MARKDOWN_LEVEL {
+        return None;
+    }
+    let text = &rest[level..];
+    if text.is_empty() {
+        return Some((level, ""));
+    }
+    text.strip_prefix([' ', '\t']).map(|text| (level, text.trim()))
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Org tags may only contain letters, digits, `_`, `@`, `#` and `%`.
+fn org_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%') { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage() -> DiaryStorage {
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(
+            NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+            String::from("# Release\n\n* shipped #work\n\n```sh\n# not a heading\n```"),
+        );
+        storage.set_entry(
+            NaiveDate::from_ymd_opt(2024, 3, 16).unwrap(),
+            String::from("Rainy day at home"),
+        );
+        storage.set_entry(
+            NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            String::from("###### Deep\nNew quarter #work/plan"),
+        );
+        storage
+    }
+
+    fn options(format: DocumentFormat) -> DocumentOptions {
+        DocumentOptions {
+            format,
+            weekday: true,
+            tags: true,
+        }
+    }
+
+    #[test]
+    fn markdown_shifts_headings_outside_code() {
+        let filter = Filter {
+            to: NaiveDate::from_ymd_opt(2024, 3, 15),
+            ..Filter::default()
+        };
+        let (document, count) = render(&storage(), &filter, &options(DocumentFormat::Markdown), Locale::En);
+
+        assert_eq!(count, 1);
+        assert_eq!(
+            document,
+            "# 2024-03-15 (Friday)\n\n`#work`\n\n## Release\n\n* shipped #work\n\n```sh\n# not a heading\n```\n"
+        );
+    }
+
+    #[test]
+    fn filters_by_tag_and_text() {
+        let by_tag = Filter {
+            tags: vec![String::from("#WORK")],
+            ..Filter::default()
+        };
+        let by_text = Filter {
+            contains: Some(String::from("rainy")),
+            ..Filter::default()
+        };
+        let markdown = options(DocumentFormat::Markdown);
+
+        assert_eq!(render(&storage(), &by_tag, &markdown, Locale::En).1, 1);
+        assert_eq!(render(&storage(), &by_text, &markdown, Locale::En).1, 1);
+        assert!(render(&storage(), &Filter::default(), &markdown, Locale::En)
+            .0
+            .contains("###### Deep"));
+    }
+
+    #[test]
+    fn org_converts_structure() {
+        let filter = Filter {
+            from: NaiveDate::from_ymd_opt(2024, 3, 15),
+            to: NaiveDate::from_ymd_opt(2024, 3, 15),
+            ..Filter::default()
+        };
+        let (document, _) = render(&storage(), &filter, &options(DocumentFormat::Org), Locale::En);
+
+        assert_eq!(
+            document,
+            "* [2024-03-15 Fri] :work:\n** Release\n\n- shipped #work\n\n#+BEGIN_SRC sh\n# not a heading\n#+END_SRC\n"
+        );
+    }
+
+    #[test]
+    fn notes_follow_the_entry_text() {
+        let mut storage = storage();
+        let notes_only = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        storage.add_note(notes_only, chrono::NaiveTime::from_hms_opt(9, 12, 0).unwrap(), String::from("rainy standup"));
+        let filter = Filter {
+            contains: Some(String::from("standup")),
+            ..Filter::default()
+        };
+        let (document, count) = render(&storage, &filter, &options(DocumentFormat::Markdown), Locale::En);
+
+        assert_eq!(count, 1);
+        assert_eq!(document, "# 2024-03-20 (Wednesday)\n\n- 09:12 rainy standup\n");
+    }
+}
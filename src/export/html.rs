@@ -0,0 +1,442 @@
+use crate::calendar::Calendar;
+use crate::holidays;
+use crate::i18n::Locale;
+use crate::storage::DiaryStorage;
+use crate::tags;
+use chrono::{Datelike, NaiveDate, Weekday};
+use pulldown_cmark::{html, Event, Options, Parser};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+const STYLESHEET: &str = r#"body {
+  font-family: -apple-system, "Segoe UI", "Hiragino Sans", "Noto Sans JP", sans-serif;
+  line-height: 1.7;
+  max-width: 56rem;
+  margin: 0 auto;
+  padding: 1rem 1.5rem 3rem;
+  color: #222;
+  background: #fdfdfd;
+}
+a { color: #1a6fb5; text-decoration: none; }
+a:hover { text-decoration: underline; }
+header nav, footer nav { display: flex; gap: 1rem; flex-wrap: wrap; margin: 1rem 0; }
+.months { display: grid; grid-template-columns: repeat(auto-fill, minmax(15rem, 1fr)); gap: 1.5rem; }
+table.month { border-collapse: collapse; width: 100%; }
+table.month caption { font-weight: bold; text-align: left; padding-bottom: .25rem; }
+table.month th, table.month td { text-align: center; padding: .15rem; width: 14%; }
+table.month td.entry a { font-weight: bold; text-decoration: underline; }
+.sun, .holiday { color: #c62828; }
+.sat { color: #1565c0; }
+.entry-body { border-top: 1px solid #ddd; margin-top: 1rem; padding-top: .5rem; }
+.entry-body pre { background: #f3f3f3; padding: .75rem; overflow-x: auto; }
+.entry-body code { background: #f3f3f3; padding: 0 .2rem; }
+.entry-body blockquote { border-left: 4px solid #ddd; margin-left: 0; padding-left: 1rem; color: #555; }
+.tags { display: flex; gap: .5rem; flex-wrap: wrap; list-style: none; padding: 0; }
+.tags li a { background: #eef4fa; border-radius: .75rem; padding: .1rem .6rem; }
+.muted { color: #888; }
+"#;
+
+/// Renders every entry to a static site in `dir`: a year index with linked
+/// calendars, one page per day with previous/next links, tag pages and a
+/// stylesheet. The output has no external dependencies. Notes are listed
+/// after the entry text, and days with only notes get a page too.
+///
+/// Returns the number of exported entries.
+pub fn export(
+    storage: &DiaryStorage,
+    dir: &Path,
+    locale: Locale,
+    week_start: Weekday,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let entries: Vec<(NaiveDate, String)> = storage
+        .days_in_range(..)
+        .into_iter()
+        .filter_map(|date| Some((date, storage.text_with_notes(&date)?)))
+        .collect();
+
+    let tag_index = storage.tag_index();
+    let mut years: BTreeMap<i32, Vec<NaiveDate>> = BTreeMap::new();
+    for (date, _) in &entries {
+        years.entry(date.year()).or_default().push(*date);
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("style.css"), STYLESHEET)?;
+    fs::write(dir.join("index.html"), index_page(&years, !tag_index.is_empty()))?;
+
+    let mut calendar = Calendar::new();
+    calendar.week_start = week_start;
+
+    for (year, dates) in &years {
+        let year_dir = dir.join(year.to_string());
+        fs::create_dir_all(&year_dir)?;
+        fs::write(
+            year_dir.join("index.html"),
+            year_page(*year, dates, &calendar, locale),
+        )?;
+    }
+
+    for (i, (date, content)) in entries.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|j| entries[j].0);
+        let next = entries.get(i + 1).map(|(d, _)| *d);
+        // Tag pages come from the tag index, which only covers entry text,
+        // so a `#tag` in a note is not linked.
+        let entry_tags = tags::extract_tags(&storage.get_entry(date).unwrap_or_default());
+        fs::write(
+            dir.join(date.year().to_string()).join(day_file(*date)),
+            day_page(*date, content, &entry_tags, prev, next, locale),
+        )?;
+    }
+
+    if !tag_index.is_empty() {
+        let tags_dir = dir.join("tags");
+        fs::create_dir_all(&tags_dir)?;
+        fs::write(tags_dir.join("index.html"), tags_index_page(tag_index))?;
+        for (tag, dates) in tag_index {
+            fs::write(tags_dir.join(tag_file(tag)), tag_page(tag, dates, storage, locale))?;
+        }
+    }
+
+    Ok(entries.len())
+}
+
+fn index_page(years: &BTreeMap<i32, Vec<NaiveDate>>, has_tags: bool) -> String {
+    let mut body = String::from("<h1>Diary</h1>\n<ul>\n");
+    for (year, dates) in years.iter().rev() {
+        body.push_str(&format!(
+            "<li><a href=\"{year}/index.html\">{year}</a> <span class=\"muted\">({})</span></li>\n",
+            dates.len()
+        ));
+    }
+    body.push_str("</ul>\n");
+    if has_tags {
+        body.push_str("<p><a href=\"tags/index.html\">#tags</a></p>\n");
+    }
+    page("Diary", "", &body)
+}
+
+fn year_page(year: i32, dates: &[NaiveDate], calendar: &Calendar, locale: Locale) -> String {
+    let mut body = format!(
+        "<header><nav><a href=\"../index.html\">&larr; Diary</a></nav></header>\n<h1>{}</h1>\n<div class=\"months\">\n",
+        year
+    );
+
+    for month in 1..=12 {
+        body.push_str(&format!(
+            "<table class=\"month\">\n<caption>{}</caption>\n<tr>",
+            escape(locale.month_name(month))
+        ));
+        for weekday in calendar.weekdays() {
+            body.push_str(&format!(
+                "<th class=\"{}\">{}</th>",
+                weekday_class(weekday),
+                escape(locale.weekday_short(weekday))
+            ));
+        }
+        body.push_str("</tr>\n");
+
+        let days = calendar.get_month_days(year, month);
+        for week in days.chunks(7) {
+            body.push_str("<tr>");
+            for day in week {
+                match day {
+                    Some(date) => {
+                        let mut classes = vec![weekday_class(date.weekday())];
+                        if holidays::is_holiday(*date) {
+                            classes.push("holiday");
+                        }
+                        if dates.binary_search(date).is_ok() {
+                            classes.push("entry");
+                            body.push_str(&format!(
+                                "<td class=\"{}\"><a href=\"{}\">{}</a></td>",
+                                classes.join(" "),
+                                day_file(*date),
+                                date.day()
+                            ));
+                        } else {
+                            body.push_str(&format!(
+                                "<td class=\"{}\">{}</td>",
+                                classes.join(" "),
+                                date.day()
+                            ));
+                        }
+                    }
+                    None => body.push_str("<td></td>"),
+                }
+            }
+            body.push_str("</tr>\n");
+        }
+        body.push_str("</table>\n");
+    }
+    body.push_str("</div>\n");
+
+    page(&year.to_string(), "../", &body)
+}
+
+fn day_page(
+    date: NaiveDate,
+    content: &str,
+    entry_tags: &[String],
+    prev: Option<NaiveDate>,
+    next: Option<NaiveDate>,
+    locale: Locale,
+) -> String {
+    let nav = day_nav(date, prev, next);
+    let heading = format!(
+        "{} ({})",
+        date.format("%Y-%m-%d"),
+        locale.weekday_long(date.weekday())
+    );
+
+    let mut body = format!("<header>{}</header>\n<h1>{}</h1>\n", nav, escape(&heading));
+    if let Some(name) = holidays::holiday_name(date) {
+        body.push_str(&format!("<p class=\"holiday\">{}</p>\n", escape(name)));
+    }
+
+    if !entry_tags.is_empty() {
+        body.push_str("<ul class=\"tags\">");
+        for tag in entry_tags {
+            body.push_str(&format!(
+                "<li><a href=\"../tags/{}\">#{}</a></li>",
+                tag_href(tag),
+                escape(tag)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    body.push_str(&format!(
+        "<article class=\"entry-body\">\n{}</article>\n<footer>{}</footer>\n",
+        render_markdown(content),
+        nav
+    ));
+
+    page(&heading, "../", &body)
+}
+
+fn day_nav(date: NaiveDate, prev: Option<NaiveDate>, next: Option<NaiveDate>) -> String {
+    let mut nav = String::from("<nav>");
+    if let Some(prev) = prev {
+        nav.push_str(&format!(
+            "<a href=\"{}\" rel=\"prev\">&larr; {}</a>",
+            day_href_from_year_dir(prev),
+            prev
+        ));
+    }
+    nav.push_str(&format!("<a href=\"index.html\">{}</a>", date.year()));
+    if let Some(next) = next {
+        nav.push_str(&format!(
+            "<a href=\"{}\" rel=\"next\">{} &rarr;</a>",
+            day_href_from_year_dir(next),
+            next
+        ));
+    }
+    nav.push_str("</nav>");
+    nav
+}
+
+fn tags_index_page(tag_index: &BTreeMap<String, BTreeSet<NaiveDate>>) -> String {
+    let mut body = String::from(
+        "<header><nav><a href=\"../index.html\">&larr; Diary</a></nav></header>\n<h1>#tags</h1>\n<ul>\n",
+    );
+    for (tag, dates) in tag_index {
+        body.push_str(&format!(
+            "<li><a href=\"{}\">#{}</a> <span class=\"muted\">({})</span></li>\n",
+            tag_href(tag),
+            escape(tag),
+            dates.len()
+        ));
+    }
+    body.push_str("</ul>\n");
+    page("#tags", "../", &body)
+}
+
+fn tag_page(tag: &str, dates: &BTreeSet<NaiveDate>, storage: &DiaryStorage, locale: Locale) -> String {
+    let mut body = format!(
+        "<header><nav><a href=\"index.html\">&larr; #tags</a></nav></header>\n<h1>#{}</h1>\n<ul>\n",
+        escape(tag)
+    );
+    for date in dates.iter().rev() {
+        let title = storage.day_title(date);
+        body.push_str(&format!(
+            "<li><a href=\"../{}/{}\">{} ({})</a> {}</li>\n",
+            date.year(),
+            day_file(*date),
+            date,
+            escape(locale.weekday_short(date.weekday())),
+            escape(title)
+        ));
+    }
+    body.push_str("</ul>\n");
+    page(&format!("#{}", tag), "../", &body)
+}
+
+fn page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{}style.css\">\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        root,
+        body
+    )
+}
+
+/// Renders Markdown to HTML. Raw HTML in entries is shown as text rather than interpreted.
+fn render_markdown(content: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let events = Parser::new_ext(content, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        event => event,
+    });
+
+    let mut output = String::new();
+    html::push_html(&mut output, events);
+    output
+}
+
+fn day_file(date: NaiveDate) -> String {
+    format!("{}.html", date.format("%Y-%m-%d"))
+}
+
+fn day_href_from_year_dir(date: NaiveDate) -> String {
+    format!("../{}/{}", date.year(), day_file(date))
+}
+
+/// File name of a tag page. Characters that are not allowed in file names
+/// and `%` itself are percent-encoded, so distinct tags never share a page.
+fn tag_file(tag: &str) -> String {
+    let mut name = String::new();
+    for c in tag.chars() {
+        match c {
+            '%' | '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => {
+                name.push_str(&format!("%{:02X}", c as u32));
+            }
+            _ => name.push(c),
+        }
+    }
+    format!("{}.html", name)
+}
+
+fn tag_href(tag: &str) -> String {
+    percent_encode(&tag_file(tag))
+}
+
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::new();
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn weekday_class(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Sun => "sun",
+        Weekday::Sat => "sat",
+        _ => "weekday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn tag_files_do_not_collide() {
+        assert_eq!(tag_file("work/plan"), "work%2Fplan.html");
+        assert_eq!(tag_file("work_plan"), "work_plan.html");
+        assert_eq!(tag_file("100%"), "100%25.html");
+        assert_eq!(tag_file("仕事"), "仕事.html");
+        // The href encodes the `%` of the file name once more for the browser.
+        assert_eq!(tag_href("work/plan"), "work%252Fplan.html");
+    }
+
+    #[test]
+    fn day_page_escapes_and_links() {
+        let page = day_page(
+            date(15),
+            "# Title\n<script>alert(1)</script> #work/plan",
+            &[String::from("work/plan")],
+            Some(date(14)),
+            None,
+            Locale::En,
+        );
+
+        assert!(page.contains("<h1>Title</h1>"));
+        assert!(page.contains("&lt;script&gt;"));
+        assert!(!page.contains("<script>"));
+        assert!(page.contains("<a href=\"../tags/work%252Fplan.html\">#work/plan</a>"));
+        assert!(page.contains("<a href=\"../2024/2024-03-14.html\" rel=\"prev\">"));
+        assert!(!page.contains("rel=\"next\""));
+    }
+
+    #[test]
+    fn tag_index_lists_counts() {
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(1), String::from("#work/plan"));
+        storage.set_entry(date(2), String::from("#work_plan #work/plan"));
+
+        let index = tags_index_page(storage.tag_index());
+        assert!(index.contains("<a href=\"work%252Fplan.html\">#work/plan</a> <span class=\"muted\">(2)</span>"));
+        assert!(index.contains("<a href=\"work_plan.html\">#work_plan</a> <span class=\"muted\">(1)</span>"));
+
+        let page = tag_page("work/plan", &storage.tag_index()["work/plan"], &storage, Locale::En);
+        assert!(page.find("2024-03-02").unwrap() < page.find("2024-03-01").unwrap());
+    }
+
+    #[test]
+    fn export_writes_a_page_per_tag() {
+        let dir = std::env::temp_dir().join(format!("diary_tui-html-{}", std::process::id()));
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(1), String::from("#work/plan"));
+        storage.set_entry(date(2), String::from("#work_plan"));
+
+        let count = export(&storage, &dir, Locale::En, Weekday::Sun).unwrap();
+        let written = |path: &str| dir.join(path).exists();
+        let result = (
+            count,
+            written("style.css"),
+            written("2024/index.html"),
+            written("2024/2024-03-02.html"),
+            written("tags/work%2Fplan.html"),
+            written("tags/work_plan.html"),
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result, (2, true, true, true, true, true));
+    }
+
+    #[test]
+    fn note_tags_are_not_linked() {
+        let dir = std::env::temp_dir().join(format!("diary_tui-html-notes-{}", std::process::id()));
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(1), String::from("#work"));
+        storage.add_note(date(1), NaiveTime::from_hms_opt(9, 0, 0).unwrap(), String::from("#cafe"));
+
+        export(&storage, &dir, Locale::En, Weekday::Sun).unwrap();
+        let page = fs::read_to_string(dir.join("2024/2024-03-01.html")).unwrap();
+        let cafe_page = dir.join("tags/cafe.html").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(page.contains("<a href=\"../tags/work.html\">#work</a>"));
+        assert!(page.contains("09:00 #cafe"));
+        assert!(!page.contains("../tags/cafe.html"));
+        assert!(!cafe_page);
+    }
+}
@@ -0,0 +1,267 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A typed per-day field declared in the config, e.g.
+/// `{"name": "sleep", "type": "number", "unit": "h"}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDef {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: FieldKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FieldKind {
+    Bool,
+    Number {
+        /// Shown after the value, e.g. `"h"`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        unit: Option<String>,
+    },
+    Enum {
+        options: Vec<String>,
+    },
+}
+
+/// A stored field value; enum fields keep the option text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FieldValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl FieldDef {
+    /// The value as shown in the form and the status bar.
+    pub fn display(&self, value: &FieldValue) -> String {
+        match (value, &self.kind) {
+            (FieldValue::Bool(true), _) => String::from("✓"),
+            (FieldValue::Bool(false), _) => String::from("✗"),
+            (FieldValue::Number(n), FieldKind::Number { unit: Some(unit) }) => format!("{}{}", n, unit),
+            (FieldValue::Number(n), _) => n.to_string(),
+            (FieldValue::Text(text), _) => text.clone(),
+        }
+    }
+
+    /// Where the value sits between 0.0 and 1.0: numbers within `range`,
+    /// enum options by their position in the list.
+    pub fn level(&self, value: &FieldValue, range: Option<(f64, f64)>) -> Option<f64> {
+        match (value, &self.kind) {
+            (FieldValue::Number(n), _) => {
+                let (min, max) = range?;
+                Some(if max > min { (n - min) / (max - min) } else { 1.0 })
+            }
+            (FieldValue::Text(text), FieldKind::Enum { options }) => {
+                let index = options.iter().position(|option| option == text)?;
+                Some(if options.len() > 1 {
+                    index as f64 / (options.len() - 1) as f64
+                } else {
+                    1.0
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The smallest and largest numbers recorded for a field.
+pub fn number_range<'a>(values: impl Iterator<Item = &'a FieldValue>) -> Option<(f64, f64)> {
+    values
+        .filter_map(|value| match value {
+            FieldValue::Number(n) => Some(*n),
+            _ => None,
+        })
+        .fold(None, |range, n| match range {
+            Some((min, max)) => Some((n.min(min), n.max(max))),
+            None => Some((n, n)),
+        })
+}
+
+/// The compact editor for one day's fields. Number fields are typed into
+/// `input`, which is parsed when the selection leaves the field.
+pub struct Form {
+    pub date: NaiveDate,
+    pub selected: usize,
+    /// One value per field definition, in config order.
+    pub values: Vec<Option<FieldValue>>,
+    /// Text typed for the selected number field.
+    pub input: String,
+}
+
+impl Form {
+    pub fn new(date: NaiveDate, defs: &[FieldDef], stored: Option<&BTreeMap<String, FieldValue>>) -> Self {
+        let values = defs
+            .iter()
+            .map(|def| stored.and_then(|stored| stored.get(&def.name)).cloned())
+            .collect();
+        let mut form = Self {
+            date,
+            selected: 0,
+            values,
+            input: String::new(),
+        };
+        form.load_input();
+        form
+    }
+
+    fn load_input(&mut self) {
+        self.input = match self.values.get(self.selected) {
+            Some(Some(FieldValue::Number(n))) => n.to_string(),
+            _ => String::new(),
+        };
+    }
+
+    /// Parses the typed number into the selected field; the error is the field name.
+    fn commit_input(&mut self, defs: &[FieldDef]) -> Result<(), String> {
+        let Some(def) = defs.get(self.selected) else {
+            return Ok(());
+        };
+        if !matches!(def.kind, FieldKind::Number { .. }) {
+            return Ok(());
+        }
+        let input = self.input.trim();
+        self.values[self.selected] = if input.is_empty() {
+            None
+        } else {
+            let n: f64 = input.parse().map_err(|_| def.name.clone())?;
+            Some(FieldValue::Number(n))
+        };
+        Ok(())
+    }
+
+    /// Moves to another field; stays put when the typed number is invalid.
+    pub fn move_selection(&mut self, defs: &[FieldDef], delta: i32) -> Result<(), String> {
+        if defs.is_empty() {
+            return Ok(());
+        }
+        self.commit_input(defs)?;
+        self.selected = (self.selected as i64 + delta as i64).clamp(0, defs.len() as i64 - 1) as usize;
+        self.load_input();
+        Ok(())
+    }
+
+    /// Flips a bool field or steps an enum field through its options.
+    pub fn cycle(&mut self, defs: &[FieldDef], delta: i32) {
+        let Some(def) = defs.get(self.selected) else {
+            return;
+        };
+        let value = &mut self.values[self.selected];
+        match &def.kind {
+            FieldKind::Bool => {
+                *value = Some(FieldValue::Bool(!matches!(value, Some(FieldValue::Bool(true)))));
+            }
+            FieldKind::Enum { options } if !options.is_empty() => {
+                let current = match value {
+                    Some(FieldValue::Text(text)) => options.iter().position(|option| option == text),
+                    _ => None,
+                };
+                let len = options.len() as i64;
+                let next = match current {
+                    Some(index) => (index as i64 + delta as i64).rem_euclid(len),
+                    None if delta < 0 => len - 1,
+                    None => 0,
+                };
+                *value = Some(FieldValue::Text(options[next as usize].clone()));
+            }
+            _ => {}
+        }
+    }
+
+    /// Types into the selected number field; other characters are ignored.
+    pub fn insert_char(&mut self, defs: &[FieldDef], c: char) {
+        let is_number = defs
+            .get(self.selected)
+            .is_some_and(|def| matches!(def.kind, FieldKind::Number { .. }));
+        if is_number && (c.is_ascii_digit() || c == '.' || c == '-') {
+            self.input.push(c);
+        }
+    }
+
+    pub fn delete_char(&mut self) {
+        self.input.pop();
+    }
+
+    pub fn clear(&mut self) {
+        if let Some(value) = self.values.get_mut(self.selected) {
+            *value = None;
+        }
+        self.input.clear();
+    }
+
+    /// The final value of every field, in config order.
+    pub fn finish(&mut self, defs: &[FieldDef]) -> Result<Vec<Option<FieldValue>>, String> {
+        self.commit_input(defs)?;
+        Ok(self.values.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defs() -> Vec<FieldDef> {
+        serde_json::from_str(
+            r#"[
+                {"name": "exercise", "type": "bool"},
+                {"name": "sleep", "type": "number", "unit": "h"},
+                {"name": "reading", "type": "enum", "options": ["none", "some", "lots"]}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn form_edits_each_kind() {
+        let defs = defs();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let stored = BTreeMap::from([(String::from("sleep"), FieldValue::Number(7.0))]);
+        let mut form = Form::new(date, &defs, Some(&stored));
+
+        form.cycle(&defs, 1);
+        form.move_selection(&defs, 1).unwrap();
+        assert_eq!(form.input, "7");
+        form.insert_char(&defs, '.');
+        form.insert_char(&defs, 'x');
+        form.insert_char(&defs, '5');
+        form.move_selection(&defs, 1).unwrap();
+        form.cycle(&defs, -1);
+
+        assert_eq!(
+            form.finish(&defs).unwrap(),
+            vec![
+                Some(FieldValue::Bool(true)),
+                Some(FieldValue::Number(7.5)),
+                Some(FieldValue::Text(String::from("lots"))),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_number_keeps_the_selection() {
+        let defs = defs();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let mut form = Form::new(date, &defs, None);
+
+        form.move_selection(&defs, 1).unwrap();
+        form.insert_char(&defs, '-');
+        assert_eq!(form.move_selection(&defs, 1), Err(String::from("sleep")));
+        assert_eq!(form.selected, 1);
+        form.clear();
+        assert_eq!(form.finish(&defs).unwrap(), vec![None, None, None]);
+    }
+
+    #[test]
+    fn levels() {
+        let defs = defs();
+        assert_eq!(defs[1].level(&FieldValue::Number(6.0), Some((4.0, 8.0))), Some(0.5));
+        assert_eq!(defs[2].level(&FieldValue::Text(String::from("some")), None), Some(0.5));
+        assert_eq!(defs[1].display(&FieldValue::Number(7.5)), "7.5h");
+        assert_eq!(
+            number_range([FieldValue::Number(3.0), FieldValue::Bool(true), FieldValue::Number(-1.0)].iter()),
+            Some((-1.0, 3.0))
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Returns the name of the Japanese public holiday on `date`, if any.
+///
+/// Covers holidays from 1949 (when the National Holidays Act took effect)
+/// through 2150, including Happy Monday rules, equinox days, substitute
+/// holidays (振替休日) and days sandwiched between two holidays (国民の休日).
+pub fn holiday_name(date: NaiveDate) -> Option<&'static str> {
+    if let Some(name) = national_holiday(date) {
+        return Some(name);
+    }
+
+    if is_substitute_holiday(date) {
+        return Some("振替休日");
+    }
+
+    if is_citizens_holiday(date) {
+        return Some("国民の休日");
+    }
+
+    None
+}
+
+pub fn is_holiday(date: NaiveDate) -> bool {
+    holiday_name(date).is_some()
+}
+
+/// Holidays defined directly by the law or by one-off special acts.
+fn national_holiday(date: NaiveDate) -> Option<&'static str> {
+    let year = date.year();
+    let month = date.month();
+    let day = date.day();
+
+    if !(1949..=2150).contains(&year) {
+        return None;
+    }
+
+    match (year, month, day) {
+        (1959, 4, 10) => return Some("皇太子明仁親王の結婚の儀"),
+        (1989, 2, 24) => return Some("昭和天皇の大喪の礼"),
+        (1990, 11, 12) => return Some("即位礼正殿の儀"),
+        (1993, 6, 9) => return Some("皇太子徳仁親王の結婚の儀"),
+        (2019, 5, 1) => return Some("天皇の即位の日"),
+        (2019, 10, 22) => return Some("即位礼正殿の儀"),
+        _ => {}
+    }
+
+    match month {
+        1 => {
+            if day == 1 {
+                return Some("元日");
+            }
+            let coming_of_age = if year >= 2000 {
+                nth_monday(year, 1, 2)
+            } else {
+                15
+            };
+            (day == coming_of_age).then_some("成人の日")
+        }
+        2 => match day {
+            11 if year >= 1967 => Some("建国記念の日"),
+            23 if year >= 2020 => Some("天皇誕生日"),
+            _ => None,
+        },
+        3 => (Some(day) == vernal_equinox_day(year)).then_some("春分の日"),
+        4 => match day {
+            29 if year >= 2007 => Some("昭和の日"),
+            29 if year >= 1989 => Some("みどりの日"),
+            29 => Some("天皇誕生日"),
+            _ => None,
+        },
+        5 => match day {
+            3 => Some("憲法記念日"),
+            4 if year >= 2007 => Some("みどりの日"),
+            5 => Some("こどもの日"),
+            _ => None,
+        },
+        7 => {
+            let marine_day = match year {
+                2020 => Some(23),
+                2021 => Some(22),
+                2003.. => Some(nth_monday(year, 7, 3)),
+                1996.. => Some(20),
+                _ => None,
+            };
+            if Some(day) == marine_day {
+                return Some("海の日");
+            }
+            match (year, day) {
+                (2020, 24) | (2021, 23) => Some("スポーツの日"),
+                _ => None,
+            }
+        }
+        8 => {
+            let mountain_day = match year {
+                2020 => Some(10),
+                2021 => Some(8),
+                2016.. => Some(11),
+                _ => None,
+            };
+            (Some(day) == mountain_day).then_some("山の日")
+        }
+        9 => {
+            let respect_for_aged = match year {
+                2003.. => Some(nth_monday(year, 9, 3)),
+                1966.. => Some(15),
+                _ => None,
+            };
+            if Some(day) == respect_for_aged {
+                return Some("敬老の日");
+            }
+            (Some(day) == autumnal_equinox_day(year)).then_some("秋分の日")
+        }
+        10 => match year {
+            2020 | 2021 => None,
+            2022.. => (day == nth_monday(year, 10, 2)).then_some("スポーツの日"),
+            2000.. => (day == nth_monday(year, 10, 2)).then_some("体育の日"),
+            1966.. => (day == 10).then_some("体育の日"),
+            _ => None,
+        },
+        11 => match day {
+            3 => Some("文化の日"),
+            23 => Some("勤労感謝の日"),
+            _ => None,
+        },
+        12 => (day == 23 && (1989..=2018).contains(&year)).then_some("天皇誕生日"),
+        _ => None,
+    }
+}
+
+/// A holiday falling on Sunday moves to the next day that is not itself a holiday
+/// (since 2007); between 1973-04-12 and 2006 it only moved to the following Monday.
+fn is_substitute_holiday(date: NaiveDate) -> bool {
+    let Some(switchover) = NaiveDate::from_ymd_opt(2007, 1, 1) else {
+        return false;
+    };
+
+    if date >= switchover {
+        let mut day = date.pred_opt();
+        while let Some(d) = day {
+            if national_holiday(d).is_none() {
+                return false;
+            }
+            if d.weekday() == Weekday::Sun {
+                return true;
+            }
+            day = d.pred_opt();
+        }
+        false
+    } else {
+        let Some(introduced) = NaiveDate::from_ymd_opt(1973, 4, 12) else {
+            return false;
+        };
+        date >= introduced
+            && date.weekday() == Weekday::Mon
+            && date.pred_opt().and_then(national_holiday).is_some()
+    }
+}
+
+/// A day that is not Sunday and lies between two holidays is a holiday itself (since 1986).
+fn is_citizens_holiday(date: NaiveDate) -> bool {
+    if date.year() < 1986 || date.weekday() == Weekday::Sun {
+        return false;
+    }
+
+    let before = date.pred_opt().and_then(national_holiday);
+    let after = date.succ_opt().and_then(national_holiday);
+    before.is_some() && after.is_some()
+}
+
+/// Returns the day of month of the `n`-th Monday of the given month.
+fn nth_monday(year: i32, month: u32, n: u32) -> u32 {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Mon, n as u8)
+        .map(|d| d.day())
+        .unwrap_or(0)
+}
+
+fn vernal_equinox_day(year: i32) -> Option<u32> {
+    let base = match year {
+        1900..=1979 => 20.8357,
+        1980..=2099 => 20.8431,
+        2100..=2150 => 21.8510,
+        _ => return None,
+    };
+    Some(equinox_day(year, base))
+}
+
+fn autumnal_equinox_day(year: i32) -> Option<u32> {
+    let base = match year {
+        1900..=1979 => 23.2588,
+        1980..=2099 => 23.2488,
+        2100..=2150 => 24.2488,
+        _ => return None,
+    };
+    Some(equinox_day(year, base))
+}
+
+/// Approximation used by the National Astronomical Observatory of Japan.
+/// Its leap-year term truncates toward zero, which matters before 1980.
+fn equinox_day(year: i32, base: f64) -> u32 {
+    let elapsed = (year - 1980) as f64;
+    let leap_offset = if year < 1980 {
+        ((year - 1983) / 4) as f64
+    } else {
+        (elapsed / 4.0).floor()
+    };
+    (base + 0.242194 * elapsed - leap_offset).floor() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(y: i32, m: u32, d: u32) -> Option<&'static str> {
+        holiday_name(NaiveDate::from_ymd_opt(y, m, d).unwrap())
+    }
+
+    #[test]
+    fn equinoxes_on_both_sides_of_1980() {
+        let cases = [
+            ((1960, 3, 20), "春分の日"),
+            ((1960, 9, 23), "秋分の日"),
+            ((1978, 3, 21), "春分の日"),
+            ((1978, 9, 23), "秋分の日"),
+            ((1979, 9, 24), "秋分の日"),
+            ((1980, 3, 20), "春分の日"),
+            ((2023, 3, 21), "春分の日"),
+            ((2024, 9, 22), "秋分の日"),
+            ((2025, 9, 23), "秋分の日"),
+        ];
+        for ((y, m, d), expected) in cases {
+            assert_eq!(name(y, m, d), Some(expected), "{}-{}-{}", y, m, d);
+        }
+        assert_eq!(name(1960, 3, 21), None);
+        assert_eq!(name(1978, 3, 22), None);
+        assert_eq!(name(1978, 9, 24), None);
+    }
+
+    #[test]
+    fn happy_monday_and_special_years() {
+        let cases = [
+            ((1999, 1, 15), Some("成人の日")),
+            ((2024, 1, 8), Some("成人の日")),
+            ((2024, 7, 15), Some("海の日")),
+            ((2024, 9, 16), Some("敬老の日")),
+            ((2019, 10, 14), Some("体育の日")),
+            ((2024, 10, 14), Some("スポーツの日")),
+            ((2019, 5, 1), Some("天皇の即位の日")),
+            ((2019, 10, 22), Some("即位礼正殿の儀")),
+            ((2019, 12, 23), None),
+            ((2020, 7, 23), Some("海の日")),
+            ((2020, 7, 24), Some("スポーツの日")),
+            ((2020, 8, 10), Some("山の日")),
+            ((2020, 10, 12), None),
+            ((2021, 7, 22), Some("海の日")),
+            ((2021, 7, 23), Some("スポーツの日")),
+            ((2021, 8, 8), Some("山の日")),
+            ((2021, 8, 11), None),
+        ];
+        for ((y, m, d), expected) in cases {
+            assert_eq!(name(y, m, d), expected, "{}-{}-{}", y, m, d);
+        }
+    }
+
+    #[test]
+    fn substitute_and_citizens_holidays() {
+        let cases = [
+            ((1973, 4, 30), Some("振替休日")),
+            ((2008, 5, 6), Some("振替休日")),
+            ((2020, 2, 24), Some("振替休日")),
+            ((2021, 8, 9), Some("振替休日")),
+            ((2024, 2, 12), Some("振替休日")),
+            ((1988, 5, 4), Some("国民の休日")),
+            ((2015, 9, 22), Some("国民の休日")),
+            ((2019, 4, 30), Some("国民の休日")),
+            ((2019, 5, 2), Some("国民の休日")),
+            ((1973, 2, 12), None),
+        ];
+        for ((y, m, d), expected) in cases {
+            assert_eq!(name(y, m, d), expected, "{}-{}-{}", y, m, d);
+        }
+    }
+}
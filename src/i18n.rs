@@ -0,0 +1,354 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// UI language. Every key in the English catalog must also exist in the Japanese one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    En,
+    Ja,
+}
+
+impl Locale {
+    /// Uses the configured locale if set, otherwise the first of `LC_ALL`,
+    /// `LC_MESSAGES` and `LANG` that is present in the environment.
+    pub fn detect(configured: Option<Locale>) -> Self {
+        if let Some(locale) = configured {
+            return locale;
+        }
+
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map(|value| Self::from_env_value(&value))
+            .unwrap_or(Locale::En)
+    }
+
+    fn from_env_value(value: &str) -> Self {
+        if value.to_lowercase().starts_with("ja") {
+            Locale::Ja
+        } else {
+            Locale::En
+        }
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Ja => JA,
+        }
+    }
+
+    /// Looks up a message, falling back to English and then to the key itself.
+    pub fn text(self, key: &'static str) -> &'static str {
+        lookup(self.catalog(), key)
+            .or_else(|| lookup(EN, key))
+            .unwrap_or(key)
+    }
+
+    /// Looks up a message and replaces its `{0}`, `{1}`, ... placeholders with `args`.
+    pub fn format(self, key: &'static str, args: &[&str]) -> String {
+        let mut message = self.text(key).to_string();
+        for (i, arg) in args.iter().enumerate() {
+            message = message.replace(&format!("{{{}}}", i), arg);
+        }
+        message
+    }
+
+    pub fn month_name(self, month: u32) -> &'static str {
+        const KEYS: [&str; 12] = [
+            "month.1", "month.2", "month.3", "month.4", "month.5", "month.6", "month.7",
+            "month.8", "month.9", "month.10", "month.11", "month.12",
+        ];
+        KEYS.get(month.wrapping_sub(1) as usize)
+            .map(|key| self.text(key))
+            .unwrap_or("?")
+    }
+
+    /// Two-column weekday name used in calendar headers.
+    pub fn weekday_short(self, weekday: Weekday) -> &'static str {
+        self.text(match weekday {
+            Weekday::Sun => "weekday.short.sun",
+            Weekday::Mon => "weekday.short.mon",
+            Weekday::Tue => "weekday.short.tue",
+            Weekday::Wed => "weekday.short.wed",
+            Weekday::Thu => "weekday.short.thu",
+            Weekday::Fri => "weekday.short.fri",
+            Weekday::Sat => "weekday.short.sat",
+        })
+    }
+
+    pub fn weekday_long(self, weekday: Weekday) -> &'static str {
+        self.text(match weekday {
+            Weekday::Sun => "weekday.long.sun",
+            Weekday::Mon => "weekday.long.mon",
+            Weekday::Tue => "weekday.long.tue",
+            Weekday::Wed => "weekday.long.wed",
+            Weekday::Thu => "weekday.long.thu",
+            Weekday::Fri => "weekday.long.fri",
+            Weekday::Sat => "weekday.long.sat",
+        })
+    }
+}
+
+fn lookup(catalog: &[(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+const EN: &[(&str, &str)] = &[
+    ("month.1", "January"),
+    ("month.2", "February"),
+    ("month.3", "March"),
+    ("month.4", "April"),
+    ("month.5", "May"),
+    ("month.6", "June"),
+    ("month.7", "July"),
+    ("month.8", "August"),
+    ("month.9", "September"),
+    ("month.10", "October"),
+    ("month.11", "November"),
+    ("month.12", "December"),
+    ("weekday.short.sun", "Su"),
+    ("weekday.short.mon", "Mo"),
+    ("weekday.short.tue", "Tu"),
+    ("weekday.short.wed", "We"),
+    ("weekday.short.thu", "Th"),
+    ("weekday.short.fri", "Fr"),
+    ("weekday.short.sat", "Sa"),
+    ("weekday.long.sun", "Sunday"),
+    ("weekday.long.mon", "Monday"),
+    ("weekday.long.tue", "Tuesday"),
+    ("weekday.long.wed", "Wednesday"),
+    ("weekday.long.thu", "Thursday"),
+    ("weekday.long.fri", "Friday"),
+    ("weekday.long.sat", "Saturday"),
+    ("calendar.month_title", "{1} {0}"),
+    ("calendar.week_column", "Wk"),
+    ("agenda.title", "Agenda ({0} entries)"),
+    ("agenda.empty", "No entries yet"),
+    ("agenda.words", "w"),
+    ("editor.notes", "Notes"),
+    ("agenda.notes", " (+{0} notes)"),
+    ("on_this_day.title", "On this day"),
+    ("on_this_day.empty", "No entries on this day in earlier years"),
+    ("on_this_day.years_ago", "{0} years ago"),
+    ("on_this_day.year_ago", "1 year ago"),
+    ("on_this_day.month_ago", "1 month ago"),
+    ("on_this_day.week_ago", "1 week ago"),
+    ("tags.title", "Tags ({0})"),
+    ("tags.empty", "No #tags yet"),
+    ("templates.title", "Templates for {0}"),
+    ("tasks.title", "Open tasks ({0})"),
+    ("backlinks.title", "Linked from"),
+    ("backlinks.empty", "No entry links to this day"),
+    ("tasks.empty", "No open - [ ] tasks"),
+    ("stats.title", "Statistics"),
+    ("stats.entries", "Entries"),
+    ("stats.total_words", "Total words"),
+    ("stats.average_words", "Average words"),
+    ("stats.current_streak", "Current streak"),
+    ("stats.longest_streak", "Longest streak"),
+    ("stats.days", "{0} days"),
+    ("stats.busiest_weekday", "Busiest weekday"),
+    ("stats.goal_days", "Days meeting goal"),
+    ("stats.average_mood", "Average mood"),
+    ("stats.mood_per_month", "Average mood per month"),
+    ("stats.busiest_value", "{0} ({1} entries)"),
+    ("stats.entries_per_month", "Entries per month"),
+    ("stats.words_per_month", "Words per month"),
+    ("editor.title", "Diary - {0} ({1})"),
+    ("form.title", "Fields - {0}"),
+    ("status.calendar", "q: quit, Enter: edit, g: go to date, i: quick note, [/]: prev/next entry, }: next gap, w: week, a: agenda, s: stats, o: on this day, b: backlinks, t: tags, T: templates, x: tasks, m: mood, f: fields, F: field overlay"),
+    ("status.week", "Week view - h/l: day, k/j: week, g: go to date, i: quick note, Enter: edit, w/Esc: back"),
+    ("status.agenda", "Agenda - j/k: next/prev entry, H/L: prev/next month, i: quick note, Enter: edit, a/Esc: back"),
+    ("status.stats", "Statistics - s/Esc: back"),
+    ("status.tags", "Tags - j/k: move, Enter: highlight on calendar, t/Esc: back"),
+    ("status.templates", "Templates - j/k: move, Enter: write with template, T/Esc: back"),
+    ("status.tasks", "Tasks - j/k: move, Enter: open entry at the task, x/Esc: back"),
+    ("status.editing", "Editing mode - Tab to save and return, Esc to cancel, Ctrl+X: toggle checkbox, Ctrl+G: follow [[link]]"),
+    ("status.saved", "Saved successfully! Press 'q' to quit"),
+    ("status.save_error", "Error saving: {0}"),
+    ("status.cancelled", "Edit cancelled"),
+    ("status.goto_invalid", "Could not understand date: {0}"),
+    ("status.no_later_entry", "No later entries"),
+    ("status.no_earlier_entry", "No earlier entries"),
+    ("status.overlay_error", "Could not load calendar overlay: {0}"),
+    ("status.streak", "Streak: {0} days"),
+    ("status.no_link", "No [[date]] link under the cursor"),
+    ("status.note_added", "Note added at {0}"),
+    ("status.no_templates", "No templates yet; add .md files to the templates folder next to config.json"),
+    ("status.template_error", "Could not load templates: {0}"),
+    ("status.mood_prompt", "Mood: press 1-{0} (0 to clear)"),
+    ("status.mood_saved", "Mood saved {0}"),
+    ("status.mood_cleared", "Mood cleared"),
+    ("status.form", "Fields - j/k: move, Space/h/l: change, 0-9: number, Del: clear, Enter: save, Esc: cancel"),
+    ("status.fields_saved", "Fields saved"),
+    ("status.field_invalid", "Not a number: {0}"),
+    ("status.no_fields", "No fields defined in the config"),
+    ("status.field_overlay", "Showing {0} on the calendar"),
+    ("status.field_overlay_off", "Field overlay off"),
+    ("prompt.goto", "Go to date: "),
+    ("prompt.note", "Quick note: "),
+];
+
+const JA: &[(&str, &str)] = &[
+    ("month.1", "1月"),
+    ("month.2", "2月"),
+    ("month.3", "3月"),
+    ("month.4", "4月"),
+    ("month.5", "5月"),
+    ("month.6", "6月"),
+    ("month.7", "7月"),
+    ("month.8", "8月"),
+    ("month.9", "9月"),
+    ("month.10", "10月"),
+    ("month.11", "11月"),
+    ("month.12", "12月"),
+    ("weekday.short.sun", "日"),
+    ("weekday.short.mon", "月"),
+    ("weekday.short.tue", "火"),
+    ("weekday.short.wed", "水"),
+    ("weekday.short.thu", "木"),
+    ("weekday.short.fri", "金"),
+    ("weekday.short.sat", "土"),
+    ("weekday.long.sun", "日曜日"),
+    ("weekday.long.mon", "月曜日"),
+    ("weekday.long.tue", "火曜日"),
+    ("weekday.long.wed", "水曜日"),
+    ("weekday.long.thu", "木曜日"),
+    ("weekday.long.fri", "金曜日"),
+    ("weekday.long.sat", "土曜日"),
+    ("calendar.month_title", "{0}/{1}"),
+    ("calendar.week_column", "週"),
+    ("agenda.title", "アジェンダ ({0}件)"),
+    ("agenda.empty", "まだ日記がありません"),
+    ("agenda.words", "語"),
+    ("editor.notes", "メモ"),
+    ("agenda.notes", " (+メモ{0}件)"),
+    ("on_this_day.title", "過去の今日"),
+    ("on_this_day.empty", "過去の同じ日の日記はありません"),
+    ("on_this_day.years_ago", "{0}年前"),
+    ("on_this_day.year_ago", "1年前"),
+    ("on_this_day.month_ago", "1ヶ月前"),
+    ("on_this_day.week_ago", "1週間前"),
+    ("tags.title", "タグ ({0}件)"),
+    ("tags.empty", "まだ #タグ がありません"),
+    ("templates.title", "{0} のテンプレート"),
+    ("tasks.title", "未完了のタスク ({0})"),
+    ("backlinks.title", "この日へのリンク"),
+    ("backlinks.empty", "この日にリンクしている日記はありません"),
+    ("tasks.empty", "未完了の - [ ] タスクはありません"),
+    ("stats.title", "統計"),
+    ("stats.entries", "日記の数"),
+    ("stats.total_words", "総語数"),
+    ("stats.average_words", "平均語数"),
+    ("stats.current_streak", "現在の連続記入"),
+    ("stats.longest_streak", "最長の連続記入"),
+    ("stats.days", "{0}日"),
+    ("stats.busiest_weekday", "最も多い曜日"),
+    ("stats.goal_days", "目標達成日数"),
+    ("stats.average_mood", "平均の気分"),
+    ("stats.mood_per_month", "月ごとの平均の気分"),
+    ("stats.busiest_value", "{0} ({1}件)"),
+    ("stats.entries_per_month", "月ごとの日記の数"),
+    ("stats.words_per_month", "月ごとの語数"),
+    ("editor.title", "日記 - {0} ({1})"),
+    ("form.title", "項目 - {0}"),
+    ("status.calendar", "q: 終了, Enter: 編集, g: 日付へ移動, i: メモ, [/]: 前/次の日記, }: 次の未記入日, w: 週表示, a: アジェンダ, s: 統計, o: 過去の今日, b: バックリンク, t: タグ, T: テンプレート, x: タスク, m: 気分, f: 項目, F: 項目の表示"),
+    ("status.week", "週表示 - h/l: 日移動, k/j: 週移動, g: 日付へ移動, i: メモ, Enter: 編集, w/Esc: 戻る"),
+    ("status.agenda", "アジェンダ - j/k: 次/前の日記, H/L: 前月/翌月, i: メモ, Enter: 編集, a/Esc: 戻る"),
+    ("status.stats", "統計 - s/Esc: 戻る"),
+    ("status.tags", "タグ - j/k: 移動, Enter: カレンダーで強調表示, t/Esc: 戻る"),
+    ("status.templates", "テンプレート - j/k: 移動, Enter: テンプレートで書く, T/Esc: 戻る"),
+    ("status.tasks", "タスク - j/k: 移動, Enter: タスクの行を編集, x/Esc: 戻る"),
+    ("status.editing", "編集モード - Tab: 保存して戻る, Esc: キャンセル, Ctrl+X: チェックボックス切り替え, Ctrl+G: [[リンク]] 先へ移動"),
+    ("status.saved", "保存しました! q で終了"),
+    ("status.save_error", "保存に失敗しました: {0}"),
+    ("status.cancelled", "編集をキャンセルしました"),
+    ("status.goto_invalid", "日付を解釈できません: {0}"),
+    ("status.no_later_entry", "これより後の日記はありません"),
+    ("status.no_earlier_entry", "これより前の日記はありません"),
+    ("status.overlay_error", "カレンダーの重ね表示を読み込めません: {0}"),
+    ("status.streak", "連続記入: {0}日"),
+    ("status.no_link", "カーソル位置に [[日付]] リンクがありません"),
+    ("status.note_added", "{0} のメモを追加しました"),
+    ("status.no_templates", "テンプレートがありません。config.json と同じ場所の templates フォルダに .md ファイルを置いてください"),
+    ("status.template_error", "テンプレートを読み込めません: {0}"),
+    ("status.mood_prompt", "気分: 1-{0} を押してください (0 で消去)"),
+    ("status.mood_saved", "気分を記録しました {0}"),
+    ("status.mood_cleared", "気分を消去しました"),
+    ("status.form", "項目 - j/k: 移動, Space/h/l: 変更, 0-9: 数値, Del: 消去, Enter: 保存, Esc: キャンセル"),
+    ("status.fields_saved", "項目を保存しました"),
+    ("status.field_invalid", "数値ではありません: {0}"),
+    ("status.no_fields", "設定に項目がありません"),
+    ("status.field_overlay", "カレンダーに {0} を表示中"),
+    ("status.field_overlay_off", "項目の表示を終了しました"),
+    ("prompt.goto", "移動先の日付: "),
+    ("prompt.note", "メモ: "),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn keys(catalog: &[(&'static str, &'static str)]) -> BTreeSet<&'static str> {
+        catalog.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn catalogs_have_the_same_keys() {
+        let en = keys(EN);
+        let ja = keys(JA);
+
+        let missing_in_ja: Vec<_> = en.difference(&ja).collect();
+        let missing_in_en: Vec<_> = ja.difference(&en).collect();
+
+        assert!(missing_in_ja.is_empty(), "missing in ja: {:?}", missing_in_ja);
+        assert!(missing_in_en.is_empty(), "missing in en: {:?}", missing_in_en);
+    }
+
+    #[test]
+    fn catalogs_have_no_duplicate_keys() {
+        assert_eq!(keys(EN).len(), EN.len());
+        assert_eq!(keys(JA).len(), JA.len());
+    }
+
+    #[test]
+    fn translations_keep_placeholders() {
+        for (key, en_message) in EN {
+            let ja_message = Locale::Ja.text(key);
+            for i in 0..4 {
+                let placeholder = format!("{{{}}}", i);
+                assert_eq!(
+                    en_message.contains(&placeholder),
+                    ja_message.contains(&placeholder),
+                    "placeholder {} differs for {}",
+                    placeholder,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn format_replaces_placeholders() {
+        assert_eq!(
+            Locale::En.format("status.save_error", &["disk full"]),
+            "Error saving: disk full"
+        );
+        assert_eq!(Locale::Ja.format("calendar.month_title", &["2026", "10月"]), "2026/10月");
+    }
+
+    #[test]
+    fn detects_japanese_from_lang() {
+        assert_eq!(Locale::from_env_value("ja_JP.UTF-8"), Locale::Ja);
+        assert_eq!(Locale::from_env_value("en_US.UTF-8"), Locale::En);
+        assert_eq!(Locale::from_env_value("C"), Locale::En);
+    }
+}
@@ -0,0 +1,170 @@
+use super::{ImportedEntry, MergePolicy};
+use crate::storage::DiaryStorage;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder layout of a daily-notes vault.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Layout {
+    /// Obsidian: `YYYY-MM-DD.md` in the given folder
+    Obsidian,
+    /// Logseq: `journals/YYYY_MM_DD.md` inside the graph folder
+    Logseq,
+}
+
+/// Reads every `YYYY-MM-DD.md` or `YYYY_MM_DD.md` file below `dir`, at any
+/// depth, so both Obsidian folders and Logseq graphs can be imported.
+pub fn import(dir: &Path) -> Result<Vec<ImportedEntry>, Box<dyn std::error::Error>> {
+    if !dir.is_dir() {
+        return Err(format!("not a directory: {}", dir.display()).into());
+    }
+
+    let mut entries = Vec::new();
+    let mut pending: Vec<PathBuf> = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for item in fs::read_dir(&current)? {
+            let path = item?.path();
+            if path.is_dir() {
+                let hidden = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.'));
+                if !hidden {
+                    pending.push(path);
+                }
+            } else if let Some(date) = note_date(&path) {
+                entries.push(ImportedEntry {
+                    date,
+                    time: None,
+                    content: fs::read_to_string(&path)?,
+                });
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Files written by [`export`], and the dates whose note already existed.
+pub struct ExportReport {
+    pub written: usize,
+    pub collisions: Vec<NaiveDate>,
+}
+
+/// Writes one Markdown file per day in the given layout, with the day's
+/// notes listed after the entry text. A file that already exists in the
+/// folder is kept, replaced or appended to according to `policy`, as on
+/// import.
+pub fn export(
+    storage: &DiaryStorage,
+    dir: &Path,
+    layout: Layout,
+    policy: MergePolicy,
+) -> Result<ExportReport, Box<dyn std::error::Error>> {
+    let (notes_dir, format) = match layout {
+        Layout::Obsidian => (dir.to_path_buf(), "%Y-%m-%d"),
+        Layout::Logseq => (dir.join("journals"), "%Y_%m_%d"),
+    };
+    fs::create_dir_all(&notes_dir)?;
+
+    let mut report = ExportReport {
+        written: 0,
+        collisions: Vec::new(),
+    };
+    for date in storage.days_in_range(..) {
+        let Some(mut text) = storage.text_with_notes(&date) else {
+            continue;
+        };
+        let path = notes_dir.join(format!("{}.md", date.format(format)));
+        if path.exists() {
+            report.collisions.push(date);
+            match policy {
+                MergePolicy::Skip => continue,
+                MergePolicy::Overwrite => {}
+                MergePolicy::Append => {
+                    let existing = fs::read_to_string(&path)?;
+                    text = format!("{}\n\n{}", existing.trim_end(), text);
+                }
+            }
+        }
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        fs::write(path, text)?;
+        report.written += 1;
+    }
+    Ok(report)
+}
+
+fn note_date(path: &Path) -> Option<NaiveDate> {
+    if path.extension()?.to_str()? != "md" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?;
+    NaiveDate::parse_from_str(stem, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(stem, "%Y_%m_%d"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("diary_tui-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn round_trips_through_a_logseq_graph() {
+        let dir = temp_dir("logseq");
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(15), String::from("- met #alice"));
+        storage.set_entry(date(16), String::from("- wrote"));
+
+        let report = export(&storage, &dir, Layout::Logseq, MergePolicy::Skip).unwrap();
+        fs::write(dir.join("journals").join("notes.md"), "not a daily note").unwrap();
+        let mut imported = import(&dir).unwrap();
+        let page = fs::read_to_string(dir.join("journals/2024_03_15.md")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(report.written, 2);
+        assert!(report.collisions.is_empty());
+        assert_eq!(page, "- met #alice\n");
+        imported.sort_by_key(|entry| entry.date);
+        let dates: Vec<NaiveDate> = imported.iter().map(|entry| entry.date).collect();
+        assert_eq!(dates, vec![date(15), date(16)]);
+    }
+
+    #[test]
+    fn existing_notes_follow_the_policy() {
+        let dir = temp_dir("obsidian");
+        fs::create_dir_all(&dir).unwrap();
+        let note = dir.join("2024-03-15.md");
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(15), String::from("from diary"));
+        storage.set_entry(date(16), String::from("new day"));
+
+        fs::write(&note, "mine\n").unwrap();
+        let skipped = export(&storage, &dir, Layout::Obsidian, MergePolicy::Skip).unwrap();
+        let kept = fs::read_to_string(&note).unwrap();
+        let appended = export(&storage, &dir, Layout::Obsidian, MergePolicy::Append).unwrap();
+        let merged = fs::read_to_string(&note).unwrap();
+        export(&storage, &dir, Layout::Obsidian, MergePolicy::Overwrite).unwrap();
+        let replaced = fs::read_to_string(&note).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!((skipped.written, skipped.collisions), (1, vec![date(15)]));
+        assert_eq!(kept, "mine\n");
+        assert_eq!(appended.written, 2);
+        assert_eq!(merged, "mine\n\nfrom diary\n");
+        assert_eq!(replaced, "from diary\n");
+    }
+}
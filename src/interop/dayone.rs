@@ -0,0 +1,168 @@
+use super::{append_missing_tags, timed_entries, ImportedEntry};
+use crate::storage::DiaryStorage;
+use crate::tags;
+use chrono::{DateTime, Local, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Journal {
+    #[serde(default)]
+    metadata: Metadata,
+    entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    version: String,
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Self {
+            version: String::from("1.0"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    #[serde(default)]
+    uuid: String,
+    creation_date: DateTime<Utc>,
+    #[serde(default)]
+    time_zone: Option<String>,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    starred: bool,
+}
+
+/// Parses a Day One JSON export (the `Journal.json` inside the exported zip).
+///
+/// Creation dates are stored in UTC; each entry is placed on the local date
+/// of its own `timeZone`, or of the system time zone when that is missing or
+/// unknown. Tags that do not already appear in the text are appended as a
+/// `#tag` line.
+pub fn import(input: &str) -> Result<Vec<ImportedEntry>, Box<dyn std::error::Error>> {
+    let journal: Journal = serde_json::from_str(input)?;
+
+    Ok(journal
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let local = match entry.time_zone.as_deref().and_then(|tz| tz.parse::<Tz>().ok()) {
+                Some(tz) => entry.creation_date.with_timezone(&tz).naive_local(),
+                None => entry.creation_date.with_timezone(&Local).naive_local(),
+            };
+
+            ImportedEntry {
+                date: local.date(),
+                time: Some(local.time()),
+                content: append_missing_tags(&unescape(&entry.text), &entry.tags),
+            }
+        })
+        .collect())
+}
+
+/// Writes all entries as a Day One JSON export. Entries have no time of day,
+/// so each is dated noon UTC, which falls on the same date in most time zones.
+/// Notes become entries of their own at their time, also in UTC.
+pub fn export(storage: &DiaryStorage) -> Result<String, Box<dyn std::error::Error>> {
+    let noon = NaiveTime::from_hms_opt(12, 0, 0).ok_or("invalid time")?;
+    let entries = timed_entries(storage, noon)
+        .into_iter()
+        .map(|record| {
+            let creation_date = record.date.and_time(record.time).and_utc();
+            Entry {
+                uuid: uuid_for(record.content, creation_date),
+                creation_date,
+                time_zone: Some(String::from("UTC")),
+                text: record.content.to_string(),
+                tags: tags::extract_tags(record.content),
+                starred: false,
+            }
+        })
+        .collect();
+
+    let journal = Journal {
+        metadata: Metadata::default(),
+        entries,
+    };
+    Ok(serde_json::to_string_pretty(&journal)?)
+}
+
+/// Day One escapes Markdown punctuation (`\.`, `\-`, `\(` ...) in its export; this removes those backslashes.
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&next) = chars.peek() {
+                if next.is_ascii_punctuation() {
+                    output.push(next);
+                    chars.next();
+                    continue;
+                }
+            }
+        }
+        output.push(c);
+    }
+    output
+}
+
+/// A stable 32-digit hex identifier, so exporting twice produces the same UUIDs.
+fn uuid_for(content: &str, creation_date: DateTime<Utc>) -> String {
+    // FNV-1a, run twice with different offsets to fill 128 bits.
+    let hash = |seed: u64| {
+        let mut h = seed;
+        for byte in creation_date.to_rfc3339().bytes().chain(content.bytes()) {
+            h ^= u64::from(byte);
+            h = h.wrapping_mul(0x100000001b3);
+        }
+        h
+    };
+    format!("{:016X}{:016X}", hash(0xcbf29ce484222325), hash(0x84222325cbf29ce4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn import_uses_entry_time_zone_and_tags() {
+        let input = r#"{
+            "metadata": {"version": "1.0"},
+            "entries": [{
+                "uuid": "ABC",
+                "creationDate": "2024-03-14T23:30:00Z",
+                "timeZone": "Asia/Tokyo",
+                "text": "Dinner at 8\\. Great\\!",
+                "tags": ["food", "friends"],
+                "starred": true
+            }]
+        }"#;
+
+        let imported = import(input).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].date, NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+        assert_eq!(imported[0].time, NaiveTime::from_hms_opt(8, 30, 0));
+        assert_eq!(imported[0].content, "Dinner at 8. Great!\n\n#food #friends");
+    }
+
+    #[test]
+    fn round_trip_keeps_dates_and_text() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date, String::from("Year end #review"));
+
+        let imported = import(&export(&storage).unwrap()).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].date, date);
+        assert_eq!(imported[0].content, "Year end #review");
+    }
+}
@@ -0,0 +1,433 @@
+use super::{append_missing_tags, ImportedEntry};
+use crate::storage::DiaryStorage;
+use crate::tags;
+use crate::text;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Content lines longer than this many octets are folded (RFC 5545, 3.1).
+const MAX_LINE_OCTETS: usize = 75;
+/// Multi-day events are shown on at most this many days in the overlay.
+const MAX_OVERLAY_DAYS: i64 = 366;
+
+/// Calendar component used for exported entries.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Component {
+    /// VJOURNAL: a journal entry attached to a date
+    Journal,
+    /// VEVENT: an all-day event, shown by more calendar apps
+    Event,
+}
+
+/// A VEVENT or VJOURNAL read from an `.ics` file.
+#[derive(Debug, PartialEq)]
+pub struct CalendarItem {
+    pub start: NaiveDateTime,
+    /// Whether `start` was a DATE value rather than a DATE-TIME.
+    pub all_day: bool,
+    /// Exclusive end date, when the item has one.
+    pub end: Option<NaiveDate>,
+    pub summary: String,
+    pub description: String,
+    pub categories: Vec<String>,
+}
+
+/// A parsed content line: `NAME;PARAM=VALUE:value`.
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Writes every day as an all-day component of an iCalendar file, with the
+/// day's notes listed after the entry text in the description.
+pub fn export(storage: &DiaryStorage, component: Component) -> String {
+    let name = match component {
+        Component::Journal => "VJOURNAL",
+        Component::Event => "VEVENT",
+    };
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//diary_tui//diary_tui//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+    ];
+
+    for date in storage.days_in_range(..) {
+        let Some(content) = storage.text_with_notes(&date) else {
+            continue;
+        };
+        lines.push(format!("BEGIN:{}", name));
+        lines.push(format!("UID:{}@diary_tui", date.format("%Y%m%d")));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        if component == Component::Event {
+            if let Some(next) = date.succ_opt() {
+                lines.push(format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+            }
+            lines.push(String::from("TRANSP:TRANSPARENT"));
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(storage.day_title(&date))));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&content)));
+
+        let categories = tags::extract_tags(&content);
+        if !categories.is_empty() {
+            let values: Vec<String> = categories.iter().map(|tag| escape_text(tag)).collect();
+            lines.push(format!("CATEGORIES:{}", values.join(",")));
+        }
+        lines.push(format!("END:{}", name));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+/// Reads the VEVENT and VJOURNAL components of an iCalendar file. Other
+/// components (time zones, alarms, to-dos) are skipped, and recurrence rules
+/// are not expanded.
+pub fn parse(input: &str) -> Result<Vec<CalendarItem>, Box<dyn std::error::Error>> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut current: Option<(usize, Vec<Property>)> = None;
+
+    for line in unfold(input) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let property = parse_line(&line).ok_or_else(|| format!("invalid iCalendar line: {}", line))?;
+
+        if property.name == "BEGIN" {
+            depth += 1;
+            let value = property.value.to_ascii_uppercase();
+            if current.is_none() && (value == "VEVENT" || value == "VJOURNAL") {
+                current = Some((depth, Vec::new()));
+            }
+        } else if property.name == "END" {
+            if let Some((component_depth, properties)) = current.take() {
+                if component_depth == depth {
+                    items.push(item_from(&properties)?);
+                } else {
+                    current = Some((component_depth, properties));
+                }
+            }
+            depth = depth.saturating_sub(1);
+        } else if let Some((component_depth, properties)) = current.as_mut() {
+            // Properties of nested components such as VALARM are not the item's own.
+            if *component_depth == depth {
+                properties.push(property);
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+/// Converts calendar items into diary entries on their start dates.
+pub fn import(input: &str) -> Result<Vec<ImportedEntry>, Box<dyn std::error::Error>> {
+    Ok(parse(input)?
+        .into_iter()
+        .map(|item| {
+            let content = if item.description.trim().is_empty() {
+                item.summary.clone()
+            } else if text::title(&item.description) == item.summary.trim() {
+                item.description.clone()
+            } else {
+                format!("{}\n\n{}", item.summary, item.description)
+            };
+
+            ImportedEntry {
+                date: item.start.date(),
+                time: (!item.all_day).then(|| item.start.time()),
+                content: append_missing_tags(&content, &item.categories),
+            }
+        })
+        .collect())
+}
+
+/// Reads the given `.ics` files into a map from date to the summaries of the
+/// items on that date, for showing alongside the diary.
+pub fn load_overlay(
+    paths: &[PathBuf],
+) -> Result<BTreeMap<NaiveDate, Vec<String>>, Box<dyn std::error::Error>> {
+    let mut overlay: BTreeMap<NaiveDate, Vec<String>> = BTreeMap::new();
+
+    for path in paths {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        let items = parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        for item in items {
+            let start = item.start.date();
+            let end = item
+                .end
+                .filter(|end| *end > start)
+                .unwrap_or_else(|| start + Duration::days(1))
+                .min(start + Duration::days(MAX_OVERLAY_DAYS));
+            let summary = if item.summary.is_empty() {
+                text::title(&item.description).to_string()
+            } else {
+                item.summary.clone()
+            };
+
+            let mut date = start;
+            while date < end {
+                overlay.entry(date).or_default().push(summary.clone());
+                date += Duration::days(1);
+            }
+        }
+    }
+
+    Ok(overlay)
+}
+
+fn item_from(properties: &[Property]) -> Result<CalendarItem, Box<dyn std::error::Error>> {
+    let find = |name: &str| properties.iter().find(|p| p.name == name);
+
+    let dtstart = find("DTSTART").ok_or("calendar item without DTSTART")?;
+    let (start, all_day) =
+        parse_date_time(dtstart).ok_or_else(|| format!("invalid DTSTART: {}", dtstart.value))?;
+
+    let end = find("DTEND").and_then(parse_date_time).map(|(end, end_all_day)| {
+        // A DATE end is exclusive; a DATE-TIME end covers its own day unless it is midnight.
+        if end_all_day || end.time() == NaiveTime::MIN {
+            end.date()
+        } else {
+            end.date() + Duration::days(1)
+        }
+    });
+
+    let categories = properties
+        .iter()
+        .filter(|p| p.name == "CATEGORIES")
+        .flat_map(|p| split_list(&p.value))
+        .map(|value| unescape_text(&value))
+        .filter(|value| !value.is_empty())
+        .collect();
+
+    Ok(CalendarItem {
+        start,
+        all_day,
+        end,
+        summary: find("SUMMARY").map(|p| unescape_text(&p.value)).unwrap_or_default(),
+        description: find("DESCRIPTION")
+            .map(|p| unescape_text(&p.value))
+            .unwrap_or_default(),
+        categories,
+    })
+}
+
+/// Parses a DATE or DATE-TIME value. UTC times and times with a `TZID` are
+/// converted to the local time zone; floating times are taken as they are.
+fn parse_date_time(property: &Property) -> Option<(NaiveDateTime, bool)> {
+    let value = property.value.trim();
+
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN), true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((Utc.from_utc_datetime(&naive).with_timezone(&Local).naive_local(), false));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let local = match property.param("TZID").and_then(|tz| tz.parse::<Tz>().ok()) {
+        Some(tz) => tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|time| time.with_timezone(&Local).naive_local())
+            .unwrap_or(naive),
+        None => naive,
+    };
+    Some((local, false))
+}
+
+/// Joins folded lines: a line starting with a space or tab continues the previous one.
+fn unfold(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in input.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits a line into `CRLF`-terminated parts of at most 75 octets, never
+/// inside a UTF-8 character.
+fn fold(line: &str) -> String {
+    let mut output = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output.push_str("\r\n");
+    output
+}
+
+fn parse_line(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let mut separator = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                separator = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let separator = separator?;
+    let (head, value) = (&line[..separator], &line[separator + 1..]);
+
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.trim_matches('"').to_string()))
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Splits a list value on commas that are not escaped.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let current = items.last_mut().unwrap();
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' => items.push(String::new()),
+            _ => items.last_mut().unwrap().push(c),
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_storage() -> DiaryStorage {
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(
+            NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+            String::from("# Release day\n\nShipped v1.2; fixed C:\\temp paths, finally.\n#work #release"),
+        );
+        storage.set_entry(
+            NaiveDate::from_ymd_opt(2024, 3, 16).unwrap(),
+            "朝から雨。".repeat(20),
+        );
+        storage
+    }
+
+    #[test]
+    fn folds_lines_at_75_octets_without_splitting_characters() {
+        let exported = export(&sample_storage(), Component::Journal);
+        for line in exported.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "line too long: {}", line);
+        }
+        assert!(exported.contains("\r\n "));
+    }
+
+    #[test]
+    fn round_trip_keeps_entries() {
+        let storage = sample_storage();
+        for component in [Component::Journal, Component::Event] {
+            let imported = import(&export(&storage, component)).unwrap();
+            let restored: Vec<(NaiveDate, String)> = imported
+                .into_iter()
+                .map(|entry| (entry.date, entry.content))
+                .collect();
+            let expected: Vec<(NaiveDate, String)> = storage
+                .entries_in_range(..)
+                .map(|(date, content)| (*date, content.clone()))
+                .collect();
+            assert_eq!(restored, expected);
+        }
+    }
+
+    #[test]
+    fn parses_events_from_other_apps() {
+        let input = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:1\r\nDTSTART;VALUE=DATE:20240320\r\nDTEND;VALUE=DATE:20240323\r\nSUMMARY:Trip to Kyo\r\n to\r\nCATEGORIES:travel,family\r\nBEGIN:VALARM\r\nDESCRIPTION:Reminder\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let items = parse(input).unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].summary, "Trip to Kyoto");
+        assert_eq!(items[0].description, "");
+        assert!(items[0].all_day);
+        assert_eq!(items[0].categories, vec!["travel", "family"]);
+
+        let imported = import(input).unwrap();
+        assert_eq!(imported[0].content, "Trip to Kyoto\n\n#travel #family");
+    }
+}
@@ -0,0 +1,206 @@
+use super::{split_title, timed_entries, ImportedEntry};
+use crate::storage::DiaryStorage;
+use crate::tags;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// jrnl stores a date and a time; entry texts only have a date, so exports
+/// use this hour. Notes keep their own time.
+const EXPORT_HOUR: u32 = 9;
+
+#[derive(Serialize, Deserialize)]
+struct JsonJournal {
+    #[serde(default)]
+    tags: BTreeMap<String, usize>,
+    entries: Vec<JsonEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonEntry {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    body: String,
+    date: String,
+    #[serde(default)]
+    time: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    starred: bool,
+}
+
+/// Parses jrnl's plain-text format, where every entry starts with a
+/// `[YYYY-MM-DD HH:MM] title` line. A trailing ` *` on the title marks a
+/// starred entry and is dropped.
+pub fn import_text(input: &str) -> Result<Vec<ImportedEntry>, Box<dyn std::error::Error>> {
+    let mut entries: Vec<ImportedEntry> = Vec::new();
+
+    for line in input.lines() {
+        if let Some((date_time, title)) = parse_header(line) {
+            let title = title.strip_suffix(" *").unwrap_or(title);
+            entries.push(ImportedEntry {
+                date: date_time.date(),
+                time: Some(date_time.time()),
+                content: format!("{}\n", title),
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            entry.content.push_str(line);
+            entry.content.push('\n');
+        } else if !line.trim().is_empty() {
+            return Err("not a jrnl file: expected a line starting with [YYYY-MM-DD HH:MM]".into());
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Parses the output of `jrnl --export json`.
+pub fn import_json(input: &str) -> Result<Vec<ImportedEntry>, Box<dyn std::error::Error>> {
+    let journal: JsonJournal = serde_json::from_str(input)?;
+
+    journal
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let date = NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d")
+                .map_err(|_| format!("invalid date in jrnl export: {}", entry.date))?;
+            let time = NaiveTime::parse_from_str(&entry.time, "%H:%M").ok();
+            let content = if entry.body.trim().is_empty() {
+                entry.title
+            } else {
+                format!("{}\n{}", entry.title, entry.body)
+            };
+            Ok(ImportedEntry { date, time, content })
+        })
+        .collect()
+}
+
+fn export_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(EXPORT_HOUR, 0, 0).unwrap_or_default()
+}
+
+/// Writes all entries in jrnl's plain-text format, each note as an entry of
+/// its own. The first line of each entry becomes its title.
+pub fn export_text(storage: &DiaryStorage) -> String {
+    let mut output = String::new();
+    for record in timed_entries(storage, export_time()) {
+        let (title, body) = split_title(record.content);
+        output.push_str(&format!("[{} {}] {}\n", record.date, record.time.format("%H:%M"), title));
+        if !body.is_empty() {
+            output.push_str(body);
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Writes all entries in the format of `jrnl --export json`, each note as an entry of its own.
+pub fn export_json(storage: &DiaryStorage) -> Result<String, Box<dyn std::error::Error>> {
+    let mut journal = JsonJournal {
+        tags: BTreeMap::new(),
+        entries: Vec::new(),
+    };
+
+    for record in timed_entries(storage, export_time()) {
+        let (title, body) = split_title(record.content);
+        let entry_tags: Vec<String> = tags::extract_tags(record.content)
+            .into_iter()
+            .map(|tag| format!("#{}", tag))
+            .collect();
+        for tag in &entry_tags {
+            *journal.tags.entry(tag.clone()).or_default() += 1;
+        }
+
+        journal.entries.push(JsonEntry {
+            title: title.to_string(),
+            body: body.to_string(),
+            date: record.date.to_string(),
+            time: record.time.format("%H:%M").to_string(),
+            tags: entry_tags,
+            starred: false,
+        });
+    }
+
+    Ok(serde_json::to_string_pretty(&journal)?)
+}
+
+/// Splits `[YYYY-MM-DD HH:MM] title` into its timestamp and title.
+fn parse_header(line: &str) -> Option<(NaiveDateTime, &str)> {
+    let rest = line.strip_prefix('[')?;
+    let (stamp, title) = rest.split_once(']')?;
+    let date_time = ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %I:%M %p"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(stamp, format).ok())?;
+    Some((date_time, title.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage_with(entries: &[(NaiveDate, &str)]) -> DiaryStorage {
+        let mut storage = DiaryStorage::new();
+        for (date, content) in entries {
+            storage.set_entry(*date, content.to_string());
+        }
+        storage
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let storage = storage_with(&[(date, "Title line\n\nBody with #work tag")]);
+
+        let imported = import_text(&export_text(&storage)).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].date, date);
+        assert_eq!(imported[0].content.trim(), "Title line\nBody with #work tag");
+    }
+
+    #[test]
+    fn text_import_reads_multiple_entries_and_stars() {
+        let input = "[2024-03-15 08:30] Morning *\nCoffee.\n\n[2024-03-15 21:00] Evening\n";
+        let imported = import_text(input).unwrap();
+
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].time, NaiveTime::from_hms_opt(8, 30, 0));
+        assert_eq!(imported[0].content.trim(), "Morning\nCoffee.");
+        assert_eq!(imported[1].content.trim(), "Evening");
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let storage = storage_with(&[(date, "Title\nBody #Work")]);
+
+        let exported = export_json(&storage).unwrap();
+        assert!(exported.contains("\"#work\""));
+
+        let imported = import_json(&exported).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].date, date);
+        assert_eq!(imported[0].content, "Title\nBody #Work");
+    }
+
+    #[test]
+    fn notes_become_timed_entries() {
+        let written = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let notes_only = NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+        let mut storage = storage_with(&[(written, "Title\nBody")]);
+        storage.add_note(written, NaiveTime::from_hms_opt(18, 40, 0).unwrap(), String::from("evening"));
+        storage.add_note(written, NaiveTime::from_hms_opt(8, 5, 0).unwrap(), String::from("early"));
+        storage.add_note(notes_only, NaiveTime::from_hms_opt(12, 0, 0).unwrap(), String::from("lunch #food"));
+
+        assert_eq!(
+            export_text(&storage),
+            "[2024-03-15 08:05] early\n\n[2024-03-15 09:00] Title\nBody\n\n[2024-03-15 18:40] evening\n\n[2024-03-16 12:00] lunch #food\n\n"
+        );
+        let imported = import_json(&export_json(&storage).unwrap()).unwrap();
+        assert_eq!(imported.len(), 4);
+        assert_eq!(imported[3].date, notes_only);
+        assert_eq!(imported[3].time, NaiveTime::from_hms_opt(12, 0, 0));
+    }
+}
@@ -0,0 +1,163 @@
+pub mod daily_notes;
+pub mod dayone;
+pub mod ical;
+pub mod jrnl;
+
+use crate::storage::DiaryStorage;
+use crate::tags;
+use chrono::{NaiveDate, NaiveTime};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// One entry read from another diary tool. Several may share a date.
+pub struct ImportedEntry {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub content: String,
+}
+
+/// One record written for a tool that keeps several timed entries per day.
+pub struct TimedEntry<'a> {
+    pub date: NaiveDate,
+    pub time: NaiveTime,
+    pub content: &'a str,
+}
+
+/// Every day as timed records: the entry text at `entry_time` and each note
+/// at its own time, in time order within the day.
+pub fn timed_entries(storage: &DiaryStorage, entry_time: NaiveTime) -> Vec<TimedEntry<'_>> {
+    let mut records = Vec::new();
+    for date in storage.days_in_range(..) {
+        let start = records.len();
+        if let Some((_, content)) = storage.entries_in_range(date..=date).next() {
+            records.push(TimedEntry {
+                date,
+                time: entry_time,
+                content,
+            });
+        }
+        records.extend(storage.notes(&date).iter().map(|note| TimedEntry {
+            date,
+            time: note.time,
+            content: &note.text,
+        }));
+        records[start..].sort_by_key(|record| record.time);
+    }
+    records
+}
+
+/// What to do when an imported date already has an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum MergePolicy {
+    /// Keep the existing entry
+    Skip,
+    /// Replace the existing entry
+    Overwrite,
+    /// Append the imported text to the existing entry
+    Append,
+}
+
+#[derive(Serialize)]
+pub struct ImportReport {
+    pub policy: MergePolicy,
+    pub dry_run: bool,
+    /// Dates that had no entry before the import.
+    pub added: Vec<NaiveDate>,
+    /// Dates that already had an entry; the policy decides what happens to them.
+    pub collisions: Vec<NaiveDate>,
+}
+
+/// Merges imported entries into `storage` according to `policy`.
+///
+/// Entries sharing a date are combined first; when there are several, each
+/// gets a `## HH:MM` heading so their times are kept. With `dry_run` the
+/// storage is left untouched and only the report is produced.
+pub fn merge(
+    storage: &mut DiaryStorage,
+    entries: Vec<ImportedEntry>,
+    policy: MergePolicy,
+    dry_run: bool,
+) -> ImportReport {
+    let mut report = ImportReport {
+        policy,
+        dry_run,
+        added: Vec::new(),
+        collisions: Vec::new(),
+    };
+
+    for (date, content) in group_by_date(entries) {
+        if storage.has_entry(&date) {
+            report.collisions.push(date);
+            if dry_run {
+                continue;
+            }
+            match policy {
+                MergePolicy::Skip => {}
+                MergePolicy::Overwrite => storage.set_entry(date, content),
+                MergePolicy::Append => storage.append_entry(date, &content),
+            }
+        } else {
+            report.added.push(date);
+            if !dry_run {
+                storage.set_entry(date, content);
+            }
+        }
+    }
+
+    report
+}
+
+fn group_by_date(entries: Vec<ImportedEntry>) -> BTreeMap<NaiveDate, String> {
+    let mut by_date: BTreeMap<NaiveDate, Vec<ImportedEntry>> = BTreeMap::new();
+    for entry in entries {
+        if !entry.content.trim().is_empty() {
+            by_date.entry(entry.date).or_default().push(entry);
+        }
+    }
+
+    by_date
+        .into_iter()
+        .map(|(date, mut day_entries)| {
+            if day_entries.len() == 1 {
+                return (date, day_entries.remove(0).content.trim().to_string());
+            }
+
+            day_entries.sort_by_key(|entry| entry.time);
+            let sections: Vec<String> = day_entries
+                .iter()
+                .map(|entry| match entry.time {
+                    Some(time) => format!("## {}\n\n{}", time.format("%H:%M"), entry.content.trim()),
+                    None => entry.content.trim().to_string(),
+                })
+                .collect();
+            (date, sections.join("\n\n"))
+        })
+        .collect()
+}
+
+/// Splits an entry into a title (its first line) and the remaining body.
+pub fn split_title(content: &str) -> (&str, &str) {
+    match content.split_once('\n') {
+        Some((title, body)) => (title.trim(), body.trim()),
+        None => (content.trim(), ""),
+    }
+}
+
+/// Appends the `tags` that do not already appear in `content` as a `#tag` line.
+pub fn append_missing_tags(content: &str, tags: &[String]) -> String {
+    let existing = tags::extract_tags(content);
+    let missing: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().replace(char::is_whitespace, "_"))
+        .filter(|tag| !tag.is_empty() && !existing.contains(&tag.to_lowercase()))
+        .map(|tag| format!("#{}", tag))
+        .collect();
+
+    if missing.is_empty() {
+        content.to_string()
+    } else {
+        format!("{}\n\n{}", content.trim_end(), missing.join(" "))
+    }
+}
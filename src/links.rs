@@ -0,0 +1,166 @@
+use crate::dateparse;
+use crate::storage::DiaryStorage;
+use chrono::{NaiveDate, Weekday};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+/// The `[[target]]` and `[[target|label]]` links in `content`, with the byte
+/// range of each whole link.
+fn find(content: &str) -> Vec<(Range<usize>, &str)> {
+    let mut links = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = content[offset..].find("[[").map(|i| offset + i) {
+        let Some(len) = content[start + 2..].find("]]") else {
+            break;
+        };
+        let inner = &content[start + 2..start + 2 + len];
+        let end = start + 2 + len + 2;
+        // A newline means the brackets belong to different things.
+        if !inner.contains('\n') {
+            let target = inner.split('|').next().unwrap_or_default().trim();
+            links.push((start..end, target));
+        }
+        offset = if inner.contains('\n') { start + 2 } else { end };
+    }
+    links
+}
+
+/// The date a link written in the entry for `date` points to. Relative links
+/// such as `[[yesterday]]` count from the entry's date, not from today.
+pub fn resolve(target: &str, date: NaiveDate, week_start: Weekday) -> Option<NaiveDate> {
+    dateparse::parse_date(target, date, week_start)
+}
+
+/// The target of the link that contains the character at `cursor`.
+pub fn link_at(content: &str, cursor: usize) -> Option<&str> {
+    let byte_pos: usize = content.chars().take(cursor).map(char::len_utf8).sum();
+    find(content)
+        .into_iter()
+        .find(|(range, _)| range.contains(&byte_pos))
+        .map(|(_, target)| target)
+}
+
+/// Dates linked from the entry for `date`, without the entry itself.
+pub fn targets(content: &str, date: NaiveDate, week_start: Weekday) -> BTreeSet<NaiveDate> {
+    find(content)
+        .into_iter()
+        .filter_map(|(_, target)| resolve(target, date, week_start))
+        .filter(|target| *target != date)
+        .collect()
+}
+
+/// The lines of an entry that link to `target`.
+pub fn mentions(content: &str, date: NaiveDate, target: NaiveDate, week_start: Weekday) -> Vec<&str> {
+    content
+        .lines()
+        .filter(|line| targets(line, date, week_start).contains(&target))
+        .collect()
+}
+
+/// Which entries link to which dates. Built once at startup and kept up to
+/// date with [`LinkIndex::update`], like the statistics.
+pub struct LinkIndex {
+    week_start: Weekday,
+    outgoing: BTreeMap<NaiveDate, BTreeSet<NaiveDate>>,
+    backlinks: BTreeMap<NaiveDate, BTreeSet<NaiveDate>>,
+}
+
+impl LinkIndex {
+    pub fn new(storage: &DiaryStorage, week_start: Weekday) -> Self {
+        let mut index = Self {
+            week_start,
+            outgoing: BTreeMap::new(),
+            backlinks: BTreeMap::new(),
+        };
+        for (date, content) in storage.entries_in_range(..) {
+            index.update(*date, Some(content));
+        }
+        index
+    }
+
+    /// Replaces the links of the entry for `date`; `None` means it was removed.
+    pub fn update(&mut self, date: NaiveDate, content: Option<&str>) {
+        for target in self.outgoing.remove(&date).unwrap_or_default() {
+            if let Some(sources) = self.backlinks.get_mut(&target) {
+                sources.remove(&date);
+                if sources.is_empty() {
+                    self.backlinks.remove(&target);
+                }
+            }
+        }
+
+        let targets = content
+            .map(|content| targets(content, date, self.week_start))
+            .unwrap_or_default();
+        for target in &targets {
+            self.backlinks.entry(*target).or_default().insert(date);
+        }
+        if !targets.is_empty() {
+            self.outgoing.insert(date, targets);
+        }
+    }
+
+    /// Dates of the entries linking to `date`.
+    pub fn backlinks(&self, date: &NaiveDate) -> Option<&BTreeSet<NaiveDate>> {
+        self.backlinks.get(date)
+    }
+
+    pub fn is_linked(&self, date: &NaiveDate) -> bool {
+        self.backlinks.contains_key(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn finds_and_resolves_links() {
+        let content = "See [[2025-03-01]] and [[yesterday|the day before]].\n[[today]] [[nonsense]] [[open\n]]";
+        let entry = date(2024, 3, 15);
+
+        assert_eq!(
+            targets(content, entry, Weekday::Sun),
+            BTreeSet::from([date(2025, 3, 1), date(2024, 3, 14)])
+        );
+        assert_eq!(link_at(content, 4), Some("2025-03-01"));
+        assert_eq!(link_at(content, 30), Some("yesterday"));
+        assert_eq!(link_at(content, 19), None);
+    }
+
+    #[test]
+    fn index_follows_changes() {
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(date(2024, 3, 15), String::from("[[2024-03-01]]"));
+        storage.set_entry(date(2024, 3, 16), String::from("[[-15d]] and [[2024-03-15]]"));
+        let mut index = LinkIndex::new(&storage, Weekday::Sun);
+
+        assert_eq!(
+            index.backlinks(&date(2024, 3, 1)),
+            Some(&BTreeSet::from([date(2024, 3, 15), date(2024, 3, 16)]))
+        );
+
+        index.update(date(2024, 3, 16), Some("nothing"));
+        index.update(date(2024, 3, 15), None);
+        assert!(!index.is_linked(&date(2024, 3, 1)));
+        assert!(!index.is_linked(&date(2024, 3, 15)));
+    }
+
+    #[test]
+    fn unresolvable_targets_are_skipped() {
+        let entry = date(2024, 3, 15);
+        let content = "[[-999999999999999d]] [[+9999999999999999w]] [[someday]] [[2024-02-30]] [[2024-03-01]]";
+        assert_eq!(resolve("-999999999999999d", entry, Weekday::Sun), None);
+        assert_eq!(resolve("someday", entry, Weekday::Sun), None);
+
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(entry, String::from(content));
+        let index = LinkIndex::new(&storage, Weekday::Sun);
+        assert_eq!(index.outgoing.get(&entry), Some(&BTreeSet::from([date(2024, 3, 1)])));
+        assert_eq!(index.backlinks.len(), 1);
+    }
+}
@@ -1,10 +1,30 @@
 mod app;
 mod calendar;
+mod cli;
+mod config;
+mod dateparse;
 mod editor;
+mod era;
+mod export;
+mod fields;
+mod holidays;
+mod i18n;
+mod interop;
+mod links;
+mod mood;
+mod on_this_day;
+mod rokuyo;
+mod stats;
 mod storage;
+mod tags;
+mod tasks;
+mod templates;
+mod text;
 mod ui;
 
-use app::{App, Mode};
+use app::{App, Mode, PromptKind};
+use clap::Parser;
+use cli::Cli;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
@@ -14,9 +34,31 @@ use ratatui::{
     backend::{Backend, CrosstermBackend},
     Terminal,
 };
-use std::io;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Some(command) = cli.command {
+        return cli::run(command, cli.json);
+    }
+
+    // The TUI needs a terminal; piped text goes through `append` explicitly.
+    if !io::stdin().is_terminal() {
+        eprintln!("Error: standard input is not a terminal. Run `diary_tui append` to add piped text to today's entry.");
+        return ExitCode::FAILURE;
+    }
+
+    if let Err(err) = run_tui() {
+        eprintln!("Error: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -48,6 +90,29 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
 
         if let Event::Key(key) = event::read()? {
             match app.mode {
+                _ if app.prompt.is_some() => match key.code {
+                    KeyCode::Char(c) => {
+                        app.prompt_insert_char(c);
+                    }
+                    KeyCode::Backspace => {
+                        app.prompt_delete_char();
+                    }
+                    KeyCode::Enter => {
+                        app.submit_prompt();
+                    }
+                    KeyCode::Esc => {
+                        app.cancel_prompt();
+                    }
+                    _ => {}
+                },
+                Mode::Calendar if app.awaiting_mood => match key.code {
+                    KeyCode::Char(c) => {
+                        app.input_mood(c);
+                    }
+                    _ => {
+                        app.cancel_mood_input();
+                    }
+                },
                 Mode::Calendar => match key.code {
                     KeyCode::Char('q') => {
                         app.should_quit = true;
@@ -70,6 +135,222 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                     KeyCode::Char('L') => {
                         app.calendar.next_month();
                     }
+                    KeyCode::Char(']') => {
+                        app.jump_to_next_entry();
+                    }
+                    KeyCode::Char('[') => {
+                        app.jump_to_prev_entry();
+                    }
+                    KeyCode::Char('}') => {
+                        app.jump_to_next_gap();
+                    }
+                    KeyCode::Char('w') => {
+                        app.switch_to_week();
+                    }
+                    KeyCode::Char('a') => {
+                        app.switch_to_agenda();
+                    }
+                    KeyCode::Char('s') => {
+                        app.switch_to_stats();
+                    }
+                    KeyCode::Char('o') => {
+                        app.toggle_on_this_day();
+                    }
+                    KeyCode::Char('b') => {
+                        app.toggle_backlinks();
+                    }
+                    KeyCode::Char('t') => {
+                        app.switch_to_tags();
+                    }
+                    KeyCode::Char('T') => {
+                        app.switch_to_templates();
+                    }
+                    KeyCode::Char('x') => {
+                        app.switch_to_tasks();
+                    }
+                    KeyCode::Char('m') => {
+                        app.start_mood_input();
+                    }
+                    KeyCode::Char('f') => {
+                        app.open_form();
+                    }
+                    KeyCode::Char('F') => {
+                        app.cycle_field_overlay();
+                    }
+                    KeyCode::Esc => {
+                        app.clear_tag_filter();
+                    }
+                    KeyCode::Char(c @ '1'..='9') if app.show_on_this_day => {
+                        app.jump_to_memory(c as usize - '1' as usize);
+                    }
+                    KeyCode::Char('g') => {
+                        app.open_prompt(PromptKind::GoTo);
+                    }
+                    KeyCode::Char('i') => {
+                        app.open_prompt(PromptKind::Note);
+                    }
+                    KeyCode::Enter => {
+                        app.switch_to_editor();
+                    }
+                    _ => {}
+                },
+                Mode::Agenda => match key.code {
+                    KeyCode::Char('q') => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.jump_to_prev_entry();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.jump_to_next_entry();
+                    }
+                    KeyCode::Char('H') => {
+                        app.agenda_prev_month();
+                    }
+                    KeyCode::Char('L') => {
+                        app.agenda_next_month();
+                    }
+                    KeyCode::Char('a') | KeyCode::Esc => {
+                        app.switch_to_calendar();
+                    }
+                    KeyCode::Char('i') => {
+                        app.open_prompt(PromptKind::Note);
+                    }
+                    KeyCode::Enter if app.has_entry(&app.calendar.selected_date) => {
+                        app.switch_to_editor();
+                    }
+                    _ => {}
+                },
+                Mode::Tags => match key.code {
+                    KeyCode::Char('q') => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.move_tag_selection(-1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.move_tag_selection(1);
+                    }
+                    KeyCode::Enter => {
+                        app.apply_tag_selection();
+                    }
+                    KeyCode::Char('t') | KeyCode::Esc => {
+                        app.switch_to_calendar();
+                    }
+                    _ => {}
+                },
+                Mode::Tasks => match key.code {
+                    KeyCode::Char('q') => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.move_task_selection(-1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.move_task_selection(1);
+                    }
+                    KeyCode::Enter => {
+                        app.open_task_selection();
+                    }
+                    KeyCode::Char('x') | KeyCode::Esc => {
+                        app.switch_to_calendar();
+                    }
+                    _ => {}
+                },
+                Mode::Templates => match key.code {
+                    KeyCode::Char('q') => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.move_template_selection(-1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.move_template_selection(1);
+                    }
+                    KeyCode::Enter => {
+                        app.apply_template_selection();
+                    }
+                    KeyCode::Char('T') | KeyCode::Esc => {
+                        app.switch_to_calendar();
+                    }
+                    _ => {}
+                },
+                Mode::Form => match key.code {
+                    KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.save_form();
+                    }
+                    KeyCode::Enter | KeyCode::Tab => {
+                        app.save_form();
+                    }
+                    KeyCode::Esc => {
+                        app.cancel_form();
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.move_form_selection(-1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.move_form_selection(1);
+                    }
+                    KeyCode::Left | KeyCode::Char('h') => {
+                        app.cycle_form_value(-1);
+                    }
+                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => {
+                        app.cycle_form_value(1);
+                    }
+                    KeyCode::Delete => {
+                        app.clear_form_value();
+                    }
+                    KeyCode::Backspace => {
+                        app.form_delete_char();
+                    }
+                    KeyCode::Char(c) => {
+                        app.form_insert_char(c);
+                    }
+                    _ => {}
+                },
+                Mode::Stats => match key.code {
+                    KeyCode::Char('q') => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Char('s') | KeyCode::Esc => {
+                        app.switch_to_calendar();
+                    }
+                    _ => {}
+                },
+                Mode::Week => match key.code {
+                    KeyCode::Char('q') => {
+                        app.should_quit = true;
+                    }
+                    KeyCode::Left | KeyCode::Char('h') => {
+                        app.calendar.move_selection(-1);
+                    }
+                    KeyCode::Right | KeyCode::Char('l') => {
+                        app.calendar.move_selection(1);
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        app.calendar.move_selection(-7);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.calendar.move_selection(7);
+                    }
+                    KeyCode::Char(']') => {
+                        app.jump_to_next_entry();
+                    }
+                    KeyCode::Char('[') => {
+                        app.jump_to_prev_entry();
+                    }
+                    KeyCode::Char('}') => {
+                        app.jump_to_next_gap();
+                    }
+                    KeyCode::Char('w') | KeyCode::Esc => {
+                        app.switch_to_calendar();
+                    }
+                    KeyCode::Char('g') => {
+                        app.open_prompt(PromptKind::GoTo);
+                    }
+                    KeyCode::Char('i') => {
+                        app.open_prompt(PromptKind::Note);
+                    }
                     KeyCode::Enter => {
                         app.switch_to_editor();
                     }
@@ -81,6 +362,12 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
                             KeyCode::Char('s') => {
                                 app.save_and_return_to_calendar();
                             }
+                            KeyCode::Char('x') => {
+                                app.editor.toggle_checkbox();
+                            }
+                            KeyCode::Char('g') => {
+                                app.follow_link();
+                            }
                             _ => {}
                         }
                     } else {
@@ -0,0 +1,13 @@
+/// Moods are ratings from 1 (worst) to `MAX` (best).
+pub const MAX: u8 = 5;
+
+/// Face shown for a mood in the status bar.
+pub fn emoji(mood: u8) -> &'static str {
+    match mood {
+        1 => "😞",
+        2 => "🙁",
+        3 => "😐",
+        4 => "🙂",
+        _ => "😄",
+    }
+}
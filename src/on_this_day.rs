@@ -0,0 +1,88 @@
+use crate::storage::DiaryStorage;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ago {
+    Years(i32),
+    Month,
+    Week,
+}
+
+/// An earlier entry related to the selected date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Memory {
+    pub date: NaiveDate,
+    pub ago: Ago,
+}
+
+/// Entries written on the same month and day in previous years, newest
+/// first, optionally preceded by the entries one week and one month earlier.
+///
+/// Only the candidate dates are looked up, so this costs one lookup per year
+/// since the first entry rather than a scan of the diary.
+pub fn memories(storage: &DiaryStorage, date: NaiveDate, include_recent: bool) -> Vec<Memory> {
+    let mut memories = Vec::new();
+
+    if include_recent {
+        let week = date - Duration::days(7);
+        if storage.has_entry(&week) {
+            memories.push(Memory { date: week, ago: Ago::Week });
+        }
+        if let Some(month) = date.checked_sub_months(Months::new(1)) {
+            if storage.has_entry(&month) {
+                memories.push(Memory { date: month, ago: Ago::Month });
+            }
+        }
+    }
+
+    let Some((first, _)) = storage.entries_in_range(..).next() else {
+        return memories;
+    };
+    for year in (first.year()..date.year()).rev() {
+        // February 29 only has a counterpart in leap years.
+        let Some(earlier) = NaiveDate::from_ymd_opt(year, date.month(), date.day()) else {
+            continue;
+        };
+        if storage.has_entry(&earlier) {
+            memories.push(Memory {
+                date: earlier,
+                ago: Ago::Years(date.year() - year),
+            });
+        }
+    }
+
+    memories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn finds_previous_years_newest_first() {
+        let mut storage = DiaryStorage::new();
+        for d in [date(2020, 2, 29), date(2022, 3, 1), date(2023, 3, 1), date(2024, 2, 22), date(2024, 1, 29)] {
+            storage.set_entry(d, String::from("entry"));
+        }
+
+        assert_eq!(
+            memories(&storage, date(2024, 3, 1), false),
+            vec![
+                Memory { date: date(2023, 3, 1), ago: Ago::Years(1) },
+                Memory { date: date(2022, 3, 1), ago: Ago::Years(2) },
+            ]
+        );
+        assert_eq!(
+            memories(&storage, date(2024, 2, 29), true),
+            vec![
+                Memory { date: date(2024, 2, 22), ago: Ago::Week },
+                Memory { date: date(2024, 1, 29), ago: Ago::Month },
+                Memory { date: date(2020, 2, 29), ago: Ago::Years(4) },
+            ]
+        );
+    }
+}
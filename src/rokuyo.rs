@@ -0,0 +1,169 @@
+use chrono::{Datelike, NaiveDate};
+use std::f64::consts::PI;
+
+const NAMES: [&str; 6] = ["大安", "赤口", "先勝", "友引", "先負", "仏滅"];
+
+/// Offset of the Julian Day from `NaiveDate::num_days_from_ce` at 00:00 UT.
+const JD_CE_OFFSET: f64 = 1_721_424.5;
+/// Japan Standard Time is UTC+9.
+const JST_OFFSET_DAYS: f64 = 9.0 / 24.0;
+const SYNODIC_MONTH: f64 = 29.530588861;
+
+/// Returns the 六曜 of `date`.
+///
+/// The rokuyō follows from the date in the traditional lunisolar calendar:
+/// `(lunar month + lunar day) % 6`. The lunar calendar is computed offline from
+/// approximate new moon times and solar longitudes in Japan Standard Time,
+/// which is accurate except for the rare new moons or solar terms that fall
+/// within a few minutes of midnight.
+pub fn rokuyo(date: NaiveDate) -> &'static str {
+    let (month, day) = lunar_month_day(date);
+    NAMES[((month + day) % 6) as usize]
+}
+
+/// Returns the lunar month (1-12, leap months share the number of the previous
+/// month) and day of the month for `date`.
+fn lunar_month_day(date: NaiveDate) -> (u32, u32) {
+    let day_number = date.num_days_from_ce() as i64;
+
+    let years_since_2000 = date.year() as f64 - 2000.0 + date.ordinal0() as f64 / 365.25;
+    let mut k = (years_since_2000 * 12.3685).floor() as i64;
+    while new_moon_day(k) > day_number {
+        k -= 1;
+    }
+    while new_moon_day(k + 1) <= day_number {
+        k += 1;
+    }
+
+    let day = (day_number - new_moon_day(k) + 1) as u32;
+    (lunation_month(k), day)
+}
+
+/// Month number of lunation `k`, determined by the principal solar term (中気) it contains.
+fn lunation_month(k: i64) -> u32 {
+    let start = jst_midnight(new_moon_day(k));
+    let end = jst_midnight(new_moon_day(k + 1));
+
+    let start_longitude = solar_longitude(start);
+    let mut end_longitude = solar_longitude(end);
+    if end_longitude < start_longitude {
+        end_longitude += 360.0;
+    }
+
+    let first_term = (start_longitude / 30.0).floor() as i64 + 1;
+    if (first_term as f64) * 30.0 > end_longitude {
+        // No principal term: a leap month takes the number of the month before it.
+        return lunation_month(k - 1);
+    }
+
+    // The term at 270° (winter solstice) falls in the 11th month.
+    let term = first_term.rem_euclid(12) as u32;
+    match (term + 2) % 12 {
+        0 => 12,
+        month => month,
+    }
+}
+
+/// Day number (as in `num_days_from_ce`) of the JST date on which new moon `k` falls.
+fn new_moon_day(k: i64) -> i64 {
+    (new_moon_jde(k as f64) + JST_OFFSET_DAYS - JD_CE_OFFSET).floor() as i64
+}
+
+fn jst_midnight(day_number: i64) -> f64 {
+    day_number as f64 + JD_CE_OFFSET - JST_OFFSET_DAYS
+}
+
+/// Julian Ephemeris Day of new moon `k` (k = 0 is the new moon of 2000-01-06),
+/// after Meeus, "Astronomical Algorithms", chapter 49.
+fn new_moon_jde(k: f64) -> f64 {
+    let t = k / 1236.85;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let t4 = t3 * t;
+
+    let jde = 2451550.09766 + SYNODIC_MONTH * k + 0.00015437 * t2 - 0.000000150 * t3
+        + 0.00000000073 * t4;
+
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let m = radians(2.5534 + 29.10535670 * k - 0.0000014 * t2 - 0.00000011 * t3);
+    let mp = radians(
+        201.5643 + 385.81693528 * k + 0.0107582 * t2 + 0.00001238 * t3 - 0.000000058 * t4,
+    );
+    let f = radians(
+        160.7108 + 390.67050284 * k - 0.0016118 * t2 - 0.00000227 * t3 + 0.000000011 * t4,
+    );
+    let omega = radians(124.7746 - 1.56375588 * k + 0.0020672 * t2 + 0.00000215 * t3);
+
+    let correction = -0.40720 * mp.sin()
+        + 0.17241 * e * m.sin()
+        + 0.01608 * (2.0 * mp).sin()
+        + 0.01039 * (2.0 * f).sin()
+        + 0.00739 * e * (mp - m).sin()
+        - 0.00514 * e * (mp + m).sin()
+        + 0.00208 * e * e * (2.0 * m).sin()
+        - 0.00111 * (mp - 2.0 * f).sin()
+        - 0.00057 * (mp + 2.0 * f).sin()
+        + 0.00056 * e * (2.0 * mp + m).sin()
+        - 0.00042 * (3.0 * mp).sin()
+        + 0.00042 * e * (m + 2.0 * f).sin()
+        + 0.00038 * e * (m - 2.0 * f).sin()
+        - 0.00024 * e * (2.0 * mp - m).sin()
+        - 0.00017 * omega.sin()
+        - 0.00007 * (mp + 2.0 * m).sin()
+        + 0.00004 * (2.0 * mp - 2.0 * f).sin()
+        + 0.00004 * (3.0 * m).sin()
+        + 0.00003 * (mp + m - 2.0 * f).sin()
+        + 0.00003 * (2.0 * mp + 2.0 * f).sin()
+        - 0.00003 * (mp + m + 2.0 * f).sin()
+        + 0.00003 * (mp - m + 2.0 * f).sin()
+        - 0.00002 * (mp - m - 2.0 * f).sin()
+        - 0.00002 * (3.0 * mp + m).sin()
+        + 0.00002 * (4.0 * mp).sin();
+
+    jde + correction
+}
+
+/// Apparent geometric longitude of the sun in degrees (Meeus, chapter 25, low accuracy).
+fn solar_longitude(jd: f64) -> f64 {
+    let t = (jd - 2451545.0) / 36525.0;
+
+    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
+    let m = radians(357.52911 + 35999.05029 * t - 0.0001537 * t * t);
+    let center = (1.914602 - 0.004817 * t - 0.000014 * t * t) * m.sin()
+        + (0.019993 - 0.000101 * t) * (2.0 * m).sin()
+        + 0.000289 * (3.0 * m).sin();
+    let omega = radians(125.04 - 1934.136 * t);
+
+    (l0 + center - 0.00569 - 0.00478 * omega.sin()).rem_euclid(360.0)
+}
+
+fn radians(degrees: f64) -> f64 {
+    degrees * PI / 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_almanac() {
+        let cases = [
+            // 旧暦1月1日 is always 先勝.
+            ((2023, 1, 22), "先勝"),
+            ((2024, 2, 10), "先勝"),
+            ((2025, 1, 29), "先勝"),
+            ((2024, 2, 11), "友引"),
+            ((2024, 1, 1), "赤口"),
+            ((2025, 1, 1), "先勝"),
+            // 旧暦8月15日 (十五夜) is always 仏滅.
+            ((2023, 9, 29), "仏滅"),
+            ((2024, 9, 17), "仏滅"),
+            // First day of 閏2月 in 2023 counts as the 2nd month.
+            ((2023, 3, 22), "友引"),
+        ];
+        for ((y, m, d), expected) in cases {
+            let date = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+            assert_eq!(rokuyo(date), expected, "{}", date);
+        }
+    }
+}
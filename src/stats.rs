@@ -0,0 +1,268 @@
+use crate::storage::DiaryStorage;
+use crate::text;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MonthStats {
+    pub entries: usize,
+    pub words: usize,
+}
+
+/// Diary statistics. Word counts are computed once per entry and the
+/// totals are adjusted with [`Stats::update`] when a single entry changes, so
+/// the whole diary is only read when the cache is built at startup.
+pub struct Stats {
+    words: BTreeMap<NaiveDate, usize>,
+    total_words: usize,
+    months: BTreeMap<(i32, u32), MonthStats>,
+    /// Entries per weekday, indexed by days from Monday.
+    weekdays: [usize; 7],
+    moods: BTreeMap<NaiveDate, u8>,
+    /// Sum and count of the moods in each month.
+    month_moods: BTreeMap<(i32, u32), (u32, u32)>,
+}
+
+impl Stats {
+    pub fn new(storage: &DiaryStorage) -> Self {
+        let mut stats = Self {
+            words: BTreeMap::new(),
+            total_words: 0,
+            months: BTreeMap::new(),
+            weekdays: [0; 7],
+            moods: BTreeMap::new(),
+            month_moods: BTreeMap::new(),
+        };
+        for (date, content) in storage.entries_in_range(..) {
+            stats.add(*date, text::word_count(content));
+        }
+        for (date, mood) in storage.moods() {
+            stats.update_mood(date, Some(mood));
+        }
+        stats
+    }
+
+    /// Replaces the cached numbers for `date` with those of `content`;
+    /// `None` means the entry was removed.
+    pub fn update(&mut self, date: NaiveDate, content: Option<&str>) {
+        if let Some(words) = self.words.remove(&date) {
+            self.total_words -= words;
+            self.weekdays[date.weekday().num_days_from_monday() as usize] -= 1;
+            let key = (date.year(), date.month());
+            if let Some(month) = self.months.get_mut(&key) {
+                month.entries -= 1;
+                month.words -= words;
+                if month.entries == 0 {
+                    self.months.remove(&key);
+                }
+            }
+        }
+
+        if let Some(content) = content.filter(|c| !c.is_empty()) {
+            self.add(date, text::word_count(content));
+        }
+    }
+
+    /// Replaces the cached mood for `date`.
+    pub fn update_mood(&mut self, date: NaiveDate, mood: Option<u8>) {
+        let key = (date.year(), date.month());
+        if let Some(old) = self.moods.remove(&date) {
+            if let Some((sum, count)) = self.month_moods.get_mut(&key) {
+                *sum -= u32::from(old);
+                *count -= 1;
+                if *count == 0 {
+                    self.month_moods.remove(&key);
+                }
+            }
+        }
+        if let Some(mood) = mood {
+            self.moods.insert(date, mood);
+            let (sum, count) = self.month_moods.entry(key).or_default();
+            *sum += u32::from(mood);
+            *count += 1;
+        }
+    }
+
+    fn add(&mut self, date: NaiveDate, words: usize) {
+        self.words.insert(date, words);
+        self.total_words += words;
+        self.weekdays[date.weekday().num_days_from_monday() as usize] += 1;
+        let month = self.months.entry((date.year(), date.month())).or_default();
+        month.entries += 1;
+        month.words += words;
+    }
+
+    pub fn total_entries(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn total_words(&self) -> usize {
+        self.total_words
+    }
+
+    pub fn average_words(&self) -> f64 {
+        if self.words.is_empty() {
+            0.0
+        } else {
+            self.total_words as f64 / self.words.len() as f64
+        }
+    }
+
+    pub fn words_on(&self, date: &NaiveDate) -> Option<usize> {
+        self.words.get(date).copied()
+    }
+
+    pub fn average_mood(&self) -> Option<f64> {
+        let (sum, count) = self
+            .month_moods
+            .values()
+            .fold((0, 0), |(sum, count), (s, c)| (sum + s, count + c));
+        (count > 0).then(|| f64::from(sum) / f64::from(count))
+    }
+
+    /// Average mood of a month, if any day in it has one.
+    pub fn month_mood(&self, year: i32, month: u32) -> Option<f64> {
+        self.month_moods
+            .get(&(year, month))
+            .map(|(sum, count)| f64::from(*sum) / f64::from(*count))
+    }
+
+    /// Number of entries with at least `goal` words.
+    pub fn days_meeting_goal(&self, goal: usize) -> usize {
+        self.words.values().filter(|words| **words >= goal).count()
+    }
+
+    /// Consecutive days with entries ending today, or yesterday when today has
+    /// not been written yet.
+    pub fn current_streak(&self, today: NaiveDate) -> usize {
+        let end = if self.words.contains_key(&today) {
+            today
+        } else {
+            today - Duration::days(1)
+        };
+
+        let mut streak = 0;
+        let mut date = end;
+        while self.words.contains_key(&date) {
+            streak += 1;
+            date -= Duration::days(1);
+        }
+        streak
+    }
+
+    pub fn longest_streak(&self) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for date in self.words.keys() {
+            current = match previous {
+                Some(prev) if *date - prev == Duration::days(1) => current + 1,
+                _ => 1,
+            };
+            longest = longest.max(current);
+            previous = Some(*date);
+        }
+        longest
+    }
+
+    /// The weekday with the most entries and its count.
+    pub fn busiest_weekday(&self) -> Option<(Weekday, usize)> {
+        let (index, count) = self
+            .weekdays
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, count)| (**count, std::cmp::Reverse(*i)))?;
+        let weekday = Weekday::try_from(index as u8).ok()?;
+        (*count > 0).then_some((weekday, *count))
+    }
+
+    /// Entries and words for every month from the first entry's month up to
+    /// and including `until`, with empty months filled in.
+    pub fn monthly(&self, until: NaiveDate) -> Vec<((i32, u32), MonthStats)> {
+        let Some(first) = self.words.keys().next() else {
+            return Vec::new();
+        };
+
+        let mut result = Vec::new();
+        let (mut year, mut month) = (first.year(), first.month());
+        let last = self
+            .months
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or((year, month))
+            .max((until.year(), until.month()));
+        while (year, month) <= last {
+            let stats = self.months.get(&(year, month)).copied().unwrap_or_default();
+            result.push(((year, month), stats));
+            (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn storage(dates: &[NaiveDate]) -> DiaryStorage {
+        let mut storage = DiaryStorage::new();
+        for d in dates {
+            storage.set_entry(*d, String::from("two words"));
+        }
+        storage
+    }
+
+    #[test]
+    fn streaks() {
+        let stats = Stats::new(&storage(&[
+            date(2024, 2, 27),
+            date(2024, 2, 28),
+            date(2024, 2, 29),
+            date(2024, 3, 1),
+            date(2024, 3, 5),
+            date(2024, 3, 6),
+        ]));
+
+        assert_eq!(stats.longest_streak(), 4);
+        assert_eq!(stats.current_streak(date(2024, 3, 6)), 2);
+        assert_eq!(stats.current_streak(date(2024, 3, 7)), 2);
+        assert_eq!(stats.current_streak(date(2024, 3, 8)), 0);
+    }
+
+    #[test]
+    fn update_matches_a_rebuild() {
+        let mut storage = storage(&[date(2024, 1, 31), date(2024, 3, 4)]);
+        let mut stats = Stats::new(&storage);
+
+        storage.set_entry(date(2024, 1, 31), String::from("now three words"));
+        stats.update(date(2024, 1, 31), Some("now three words"));
+        storage.set_entry(date(2024, 3, 4), String::new());
+        stats.update(date(2024, 3, 4), None);
+
+        let rebuilt = Stats::new(&storage);
+        assert_eq!(stats.total_entries(), rebuilt.total_entries());
+        assert_eq!(stats.total_words(), 3);
+        assert_eq!(stats.monthly(date(2024, 3, 1)), rebuilt.monthly(date(2024, 3, 1)));
+        assert_eq!(stats.busiest_weekday(), Some((Weekday::Wed, 1)));
+        assert_eq!(stats.monthly(date(2024, 3, 1)).len(), 3);
+    }
+
+    #[test]
+    fn mood_averages() {
+        let mut stats = Stats::new(&DiaryStorage::new());
+        stats.update_mood(date(2024, 3, 1), Some(2));
+        stats.update_mood(date(2024, 3, 2), Some(5));
+        stats.update_mood(date(2024, 3, 2), Some(4));
+        stats.update_mood(date(2024, 4, 1), Some(1));
+        stats.update_mood(date(2024, 4, 1), None);
+
+        assert_eq!(stats.month_mood(2024, 3), Some(3.0));
+        assert_eq!(stats.month_mood(2024, 4), None);
+        assert_eq!(stats.average_mood(), Some(3.0));
+    }
+}
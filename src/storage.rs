@@ -1,19 +1,142 @@
-use chrono::NaiveDate;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::fields::FieldValue;
+use crate::tags;
+use crate::text;
+use chrono::{NaiveDate, NaiveTime};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::btree_map;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 use std::fs;
+use std::ops::{Bound, RangeBounds};
 use std::path::PathBuf;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DiaryStorage {
+/// Structured data kept for a date alongside the entry text.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryMetadata {
+    /// Mood rating from 1 (worst) to [`mood::MAX`](crate::mood::MAX) (best).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mood: Option<u8>,
+    /// Values of the fields declared in the config, by field name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, FieldValue>,
+    /// Timestamped notes added during the day, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<Note>,
+}
+
+impl EntryMetadata {
+    fn is_empty(&self) -> bool {
+        self.mood.is_none() && self.fields.is_empty() && self.notes.is_empty()
+    }
+}
+
+/// A short note kept as its own record next to the day's entry text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    #[serde(with = "hour_minute")]
+    pub time: NaiveTime,
+    pub text: String,
+}
+
+impl fmt::Display for Note {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.time.format("%H:%M"), self.text)
+    }
+}
+
+/// Stores note times as `HH:MM`.
+mod hour_minute {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%H:%M";
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&time.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&text, FORMAT)
+            .or_else(|_| NaiveTime::parse_from_str(&text, "%H:%M:%S"))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// How a date is stored in `diary.json`: just the text, as in older files,
+/// or an object with the text and its metadata once it has any.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredEntry {
+    Plain(String),
+    Rich(RichEntry),
+}
+
+#[derive(Deserialize)]
+struct RichEntry {
+    #[serde(default)]
+    content: String,
     #[serde(flatten)]
-    entries: HashMap<String, String>,
+    metadata: EntryMetadata,
+}
+
+#[derive(Serialize)]
+struct RichEntryRef<'a> {
+    #[serde(skip_serializing_if = "str::is_empty")]
+    content: &'a str,
+    #[serde(flatten)]
+    metadata: &'a EntryMetadata,
+}
+
+#[derive(Debug)]
+pub struct DiaryStorage {
+    /// Entry text; dates without text are not in this map.
+    entries: BTreeMap<NaiveDate, String>,
+    /// Metadata for the dates that have any, with or without text.
+    metadata: BTreeMap<NaiveDate, EntryMetadata>,
+    /// Dates of the entries containing each `#tag`, kept in sync by every method that changes an entry.
+    tag_index: BTreeMap<String, BTreeSet<NaiveDate>>,
+}
+
+impl Serialize for DiaryStorage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let dates: BTreeSet<&NaiveDate> = self.entries.keys().chain(self.metadata.keys()).collect();
+        let mut map = serializer.serialize_map(Some(dates.len()))?;
+        for date in dates {
+            let content = self.entries.get(date).map(String::as_str).unwrap_or_default();
+            match self.metadata.get(date) {
+                Some(metadata) => map.serialize_entry(date, &RichEntryRef { content, metadata })?,
+                None => map.serialize_entry(date, content)?,
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DiaryStorage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = BTreeMap::<NaiveDate, StoredEntry>::deserialize(deserializer)?;
+        let mut storage = Self::new();
+        for (date, entry) in stored {
+            match entry {
+                StoredEntry::Plain(content) => storage.set_entry(date, content),
+                StoredEntry::Rich(rich) => {
+                    storage.set_entry(date, rich.content);
+                    storage.set_metadata(date, rich.metadata);
+                }
+            }
+        }
+        Ok(storage)
+    }
 }
 
 impl DiaryStorage {
     pub fn new() -> Self {
         Self {
-            entries: HashMap::new(),
+            entries: BTreeMap::new(),
+            metadata: BTreeMap::new(),
+            tag_index: BTreeMap::new(),
         }
     }
 
@@ -42,28 +165,380 @@ impl DiaryStorage {
     }
 
     pub fn get_entry(&self, date: &NaiveDate) -> Option<String> {
-        let key = date.format("%Y-%m-%d").to_string();
-        self.entries.get(&key).cloned()
+        self.entries.get(date).cloned()
     }
 
     pub fn set_entry(&mut self, date: NaiveDate, content: String) {
-        let key = date.format("%Y-%m-%d").to_string();
+        self.unindex_tags(date);
         if content.is_empty() {
-            self.entries.remove(&key);
+            self.entries.remove(&date);
+        } else {
+            self.entries.insert(date, content);
+            self.index_tags(date);
+        }
+    }
+
+    /// Appends `text` to the entry on `date` as a new paragraph, creating the entry if needed.
+    pub fn append_entry(&mut self, date: NaiveDate, text: &str) {
+        let text = text.trim_end_matches('\n');
+        if text.is_empty() {
+            return;
+        }
+
+        let content = match self.entries.get(&date) {
+            Some(existing) => format!("{}\n\n{}", existing.trim_end_matches('\n'), text),
+            None => text.to_string(),
+        };
+        self.set_entry(date, content);
+    }
+
+    /// Removes the entry on `date` together with its metadata, returning whether there was anything.
+    pub fn remove_entry(&mut self, date: &NaiveDate) -> bool {
+        self.unindex_tags(*date);
+        let had_metadata = self.metadata.remove(date).is_some();
+        self.entries.remove(date).is_some() || had_metadata
+    }
+
+    pub fn metadata(&self, date: &NaiveDate) -> Option<&EntryMetadata> {
+        self.metadata.get(date)
+    }
+
+    /// Replaces the metadata for `date`; empty metadata is removed.
+    pub fn set_metadata(&mut self, date: NaiveDate, metadata: EntryMetadata) {
+        if metadata.is_empty() {
+            self.metadata.remove(&date);
         } else {
-            self.entries.insert(key, content);
+            self.metadata.insert(date, metadata);
+        }
+    }
+
+    pub fn mood(&self, date: &NaiveDate) -> Option<u8> {
+        self.metadata(date).and_then(|metadata| metadata.mood)
+    }
+
+    pub fn set_mood(&mut self, date: NaiveDate, mood: Option<u8>) {
+        let mut metadata = self.metadata(&date).cloned().unwrap_or_default();
+        metadata.mood = mood;
+        self.set_metadata(date, metadata);
+    }
+
+    /// Sets (or with `None` removes) the value of one field on `date`.
+    pub fn set_field(&mut self, date: NaiveDate, name: &str, value: Option<FieldValue>) {
+        let mut metadata = self.metadata(&date).cloned().unwrap_or_default();
+        match value {
+            Some(value) => metadata.fields.insert(name.to_string(), value),
+            None => metadata.fields.remove(name),
+        };
+        self.set_metadata(date, metadata);
+    }
+
+    /// The notes of `date` in time order.
+    pub fn notes(&self, date: &NaiveDate) -> &[Note] {
+        self.metadata(date).map(|metadata| metadata.notes.as_slice()).unwrap_or_default()
+    }
+
+    pub fn has_notes(&self, date: &NaiveDate) -> bool {
+        !self.notes(date).is_empty()
+    }
+
+    /// The entry text followed by a `- HH:MM text` list of the notes, as
+    /// exports write a day; `None` when the day has neither.
+    pub fn text_with_notes(&self, date: &NaiveDate) -> Option<String> {
+        let content = self.entries.get(date).map(String::as_str).unwrap_or_default();
+        let notes = self.notes(date);
+        if notes.is_empty() {
+            return self.entries.get(date).cloned();
+        }
+
+        let mut text = content.trim_end().to_string();
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        for note in notes {
+            text.push_str(&format!("- {}\n", note));
+        }
+        Some(text)
+    }
+
+    /// The first line of the entry, or the first note of a day without text.
+    pub fn day_title(&self, date: &NaiveDate) -> &str {
+        match self.entries.get(date).map(|content| text::title(content)) {
+            Some(title) if !title.is_empty() => title,
+            _ => self.notes(date).first().map(|note| note.text.as_str()).unwrap_or_default(),
+        }
+    }
+
+    /// Adds a note to `date`, after any notes with the same or an earlier time.
+    pub fn add_note(&mut self, date: NaiveDate, time: NaiveTime, text: String) {
+        let mut metadata = self.metadata(&date).cloned().unwrap_or_default();
+        let index = metadata.notes.partition_point(|note| note.time <= time);
+        metadata.notes.insert(index, Note { time, text });
+        self.set_metadata(date, metadata);
+    }
+
+    /// Dates in `range` that have a value for the field `name`, with the value.
+    pub fn field_values<R: RangeBounds<NaiveDate>>(
+        &self,
+        name: &str,
+        range: R,
+    ) -> impl Iterator<Item = (NaiveDate, &FieldValue)> + '_ {
+        let name = name.to_string();
+        self.metadata
+            .range(range)
+            .filter_map(move |(date, metadata)| metadata.fields.get(&name).map(|value| (*date, value)))
+    }
+
+    /// Metadata of the dates in `range`, in chronological order.
+    pub fn metadata_in_range<R: RangeBounds<NaiveDate>>(
+        &self,
+        range: R,
+    ) -> btree_map::Range<'_, NaiveDate, EntryMetadata> {
+        self.metadata.range(range)
+    }
+
+    /// Iterates over the dates that have a mood, in chronological order.
+    pub fn moods(&self) -> impl Iterator<Item = (NaiveDate, u8)> + '_ {
+        self.metadata
+            .iter()
+            .filter_map(|(date, metadata)| metadata.mood.map(|mood| (*date, mood)))
+    }
+
+    /// All tags with the dates of the entries that use them, in tag order.
+    pub fn tag_index(&self) -> &BTreeMap<String, BTreeSet<NaiveDate>> {
+        &self.tag_index
+    }
+
+    /// Dates of the entries tagged with `tag` (lowercase, without `#`).
+    pub fn dates_with_tag(&self, tag: &str) -> Option<&BTreeSet<NaiveDate>> {
+        self.tag_index.get(tag)
+    }
+
+    fn index_tags(&mut self, date: NaiveDate) {
+        let Some(content) = self.entries.get(&date) else {
+            return;
+        };
+        for tag in tags::extract_tags(content) {
+            self.tag_index.entry(tag).or_default().insert(date);
+        }
+    }
+
+    fn unindex_tags(&mut self, date: NaiveDate) {
+        let Some(content) = self.entries.get(&date) else {
+            return;
+        };
+        for tag in tags::extract_tags(content) {
+            if let Some(dates) = self.tag_index.get_mut(&tag) {
+                dates.remove(&date);
+                if dates.is_empty() {
+                    self.tag_index.remove(&tag);
+                }
+            }
         }
     }
 
     pub fn has_entry(&self, date: &NaiveDate) -> bool {
-        let key = date.format("%Y-%m-%d").to_string();
-        self.entries.contains_key(&key)
+        self.entries.contains_key(date)
+    }
+
+    /// Iterates over the entries whose dates fall within `range`, in chronological order.
+    pub fn entries_in_range<R>(&self, range: R) -> btree_map::Range<'_, NaiveDate, String>
+    where
+        R: RangeBounds<NaiveDate>,
+    {
+        self.entries.range(range)
     }
 
-    fn get_storage_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    /// Dates in `range` that have entry text or notes, in chronological order.
+    pub fn days_in_range<R>(&self, range: R) -> Vec<NaiveDate>
+    where
+        R: RangeBounds<NaiveDate> + Clone,
+    {
+        let mut days: BTreeSet<NaiveDate> = self.entries.range(range.clone()).map(|(date, _)| *date).collect();
+        days.extend(
+            self.metadata
+                .range(range)
+                .filter(|(_, metadata)| !metadata.notes.is_empty())
+                .map(|(date, _)| *date),
+        );
+        days.into_iter().collect()
+    }
+
+    /// Returns the date of the last entry strictly before `date`.
+    pub fn prev_entry_date(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        self.entries.range(..*date).next_back().map(|(d, _)| *d)
+    }
+
+    /// Whether `date` has entry text or notes.
+    pub fn is_written(&self, date: &NaiveDate) -> bool {
+        self.has_entry(date) || self.has_notes(date)
+    }
+
+    /// Number of days with entry text or notes.
+    pub fn written_count(&self) -> usize {
+        let notes_only = self
+            .metadata
+            .iter()
+            .filter(|(date, metadata)| !metadata.notes.is_empty() && !self.entries.contains_key(date))
+            .count();
+        self.entries.len() + notes_only
+    }
+
+    /// The first day strictly after `date` with entry text or notes; the
+    /// next of [`Self::days_in_range`] without collecting the rest.
+    pub fn next_written_date(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let after = (Bound::Excluded(*date), Bound::Unbounded);
+        let entry = self.entries.range(after).next().map(|(d, _)| *d);
+        let note = self
+            .metadata
+            .range(after)
+            .find(|(_, metadata)| !metadata.notes.is_empty())
+            .map(|(d, _)| *d);
+        entry.into_iter().chain(note).min()
+    }
+
+    /// The last day strictly before `date` with entry text or notes.
+    pub fn prev_written_date(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let entry = self.entries.range(..*date).next_back().map(|(d, _)| *d);
+        let note = self
+            .metadata
+            .range(..*date)
+            .rev()
+            .find(|(_, metadata)| !metadata.notes.is_empty())
+            .map(|(d, _)| *d);
+        entry.into_iter().chain(note).max()
+    }
+
+    /// Returns the first date after `date` that has neither text nor notes,
+    /// skipping over the run of written days that follows it. From an
+    /// unwritten date the run starts at the next written day, so repeated
+    /// jumps go from gap to gap; `None` when no written day follows.
+    pub fn next_missing_date(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let mut candidate = if self.is_written(date) {
+            date.succ_opt()?
+        } else {
+            self.next_written_date(date)?
+        };
+        while self.is_written(&candidate) {
+            candidate = candidate.succ_opt()?;
+        }
+        Some(candidate)
+    }
+
+    pub fn get_storage_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let dirs = directories::ProjectDirs::from("", "", "DiaryTui")
             .ok_or("Failed to get project directory")?;
         let data_dir = dirs.data_dir();
         Ok(data_dir.join("diary.json"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_index_follows_changes() {
+        let first = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let second = NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+        let mut storage = DiaryStorage::new();
+
+        storage.set_entry(first, String::from("#仕事 and #work"));
+        storage.append_entry(second, "#work");
+        assert_eq!(storage.dates_with_tag("work").map(|d| d.len()), Some(2));
+        assert!(storage.dates_with_tag("仕事").is_some());
+
+        storage.set_entry(first, String::from("no tags now"));
+        storage.remove_entry(&second);
+        assert!(storage.tag_index().is_empty());
+
+        let json = serde_json::to_string(&storage).unwrap();
+        assert_eq!(json, r#"{"2024-03-15":"no tags now"}"#);
+    }
+
+    #[test]
+    fn metadata_round_trips_next_to_plain_entries() {
+        let json = r##"{"2024-03-14":"plain","2024-03-15":{"content":"#rich text","mood":4,"notes":[{"time":"09:12","text":"standup"},{"time":"18:40","text":"reflection"}]},"2024-03-16":{"mood":2,"fields":{"exercise":true,"reading":"some","sleep":7.5}}}"##;
+        let storage: DiaryStorage = serde_json::from_str(json).unwrap();
+
+        let rich = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let mood_only = NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+        assert_eq!(storage.get_entry(&rich).as_deref(), Some("#rich text"));
+        assert_eq!(storage.mood(&rich), Some(4));
+        assert!(!storage.has_entry(&mood_only));
+        assert_eq!(storage.mood(&mood_only), Some(2));
+        assert_eq!(
+            storage.field_values("sleep", ..).collect::<Vec<_>>(),
+            vec![(mood_only, &FieldValue::Number(7.5))]
+        );
+        assert_eq!(storage.written_count(), 2);
+        assert!(storage.dates_with_tag("rich").is_some());
+        assert_eq!(storage.notes(&rich)[1].text, "reflection");
+
+        assert_eq!(serde_json::to_string(&storage).unwrap(), json);
+    }
+
+    #[test]
+    fn jumps_between_entries_and_gaps() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let mut storage = DiaryStorage::new();
+        assert_eq!(storage.next_written_date(&date(1)), None);
+        assert_eq!(storage.prev_entry_date(&date(1)), None);
+        assert_eq!(storage.next_missing_date(&date(1)), None);
+
+        for day in [1, 2, 3, 10] {
+            storage.set_entry(date(day), String::from("text"));
+        }
+        assert_eq!(storage.next_written_date(&date(3)), Some(date(10)));
+        assert_eq!(storage.next_written_date(&date(10)), None);
+        assert_eq!(storage.prev_entry_date(&date(10)), Some(date(3)));
+        assert_eq!(storage.prev_entry_date(&date(1)), None);
+
+        assert_eq!(storage.next_missing_date(&date(1)), Some(date(4)));
+        assert_eq!(storage.next_missing_date(&date(3)), Some(date(4)));
+        assert_eq!(storage.next_missing_date(&date(10)), Some(date(11)));
+        // The gap 3/4-3/9 starts on the selected date: skip it and the run at 3/10.
+        assert_eq!(storage.next_missing_date(&date(4)), Some(date(11)));
+        assert_eq!(storage.next_missing_date(&date(11)), None);
+
+        // A day with only notes counts as written.
+        let time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+        storage.add_note(date(11), time, String::from("note"));
+        storage.add_note(date(20), time, String::from("note"));
+        assert_eq!(storage.next_written_date(&date(10)), Some(date(11)));
+        assert_eq!(storage.next_written_date(&date(11)), Some(date(20)));
+        assert_eq!(storage.prev_written_date(&date(20)), Some(date(11)));
+        assert_eq!(storage.prev_written_date(&date(4)), Some(date(3)));
+        assert_eq!(storage.next_missing_date(&date(10)), Some(date(12)));
+        assert_eq!(storage.next_missing_date(&date(15)), Some(date(21)));
+        assert_eq!(storage.written_count(), 6);
+    }
+
+    #[test]
+    fn notes_stay_in_time_order() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let mut storage = DiaryStorage::new();
+        storage.add_note(date, time(18, 40), String::from("evening"));
+        storage.add_note(date, time(9, 12), String::from("morning"));
+        storage.add_note(date, time(9, 12), String::from("same minute"));
+
+        let texts: Vec<&str> = storage.notes(&date).iter().map(|note| note.text.as_str()).collect();
+        assert_eq!(texts, vec!["morning", "same minute", "evening"]);
+        assert!(storage.has_notes(&date));
+        assert!(!storage.has_entry(&date));
+        assert_eq!(storage.day_title(&date), "morning");
+        assert_eq!(
+            storage.text_with_notes(&date).as_deref(),
+            Some("- 09:12 morning\n- 09:12 same minute\n- 18:40 evening\n")
+        );
+
+        let written = date.succ_opt().unwrap();
+        storage.set_entry(written, String::from("# Day\nbody\n"));
+        assert_eq!(storage.text_with_notes(&written).as_deref(), Some("# Day\nbody\n"));
+        storage.add_note(written, time(7, 5), String::from("early"));
+        assert_eq!(storage.text_with_notes(&written).as_deref(), Some("# Day\nbody\n\n- 07:05 early\n"));
+        assert_eq!(storage.day_title(&written), "Day");
+        assert_eq!(storage.days_in_range(date..), vec![date, written]);
+        assert_eq!(storage.days_in_range(written..), vec![written]);
+    }
+}
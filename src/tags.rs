@@ -0,0 +1,96 @@
+/// Extracts the `#tags` of an entry body, lowercased and in order of first appearance.
+///
+/// A tag is a `#` at the start of a line or after whitespace, followed by
+/// letters, digits (including Japanese characters such as `#仕事`), `_`, `-`
+/// or `/`. Trailing `-` and `/` are not part of the tag. Heading lines
+/// (`## Meeting #work`), tags made only of digits (`#1`), inline code spans
+/// and fenced code blocks are ignored.
+pub fn extract_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || is_heading(line) {
+            continue;
+        }
+
+        for tag in line_tags(line) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+    }
+
+    tags
+}
+
+fn line_tags(line: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    let mut in_code = false;
+    let mut prev: Option<char> = None;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '`' {
+            in_code = !in_code;
+        } else if c == '#' && !in_code && prev.is_none_or(char::is_whitespace) {
+            let mut tag = String::new();
+            while let Some(&next) = chars.peek() {
+                if !is_tag_char(next) {
+                    break;
+                }
+                tag.push(next);
+                chars.next();
+            }
+
+            prev = tag.chars().last().or(Some(c));
+            let tag = tag.trim_end_matches(['-', '/']);
+            if !tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit()) {
+                tags.push(tag.to_lowercase());
+            }
+            continue;
+        }
+        prev = Some(c);
+    }
+
+    tags
+}
+
+/// An ATX heading: up to three spaces, one to six `#`, then a space or the end of the line.
+fn is_heading(line: &str) -> bool {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let rest = &line[indent..];
+    let level = rest.len() - rest.trim_start_matches('#').len();
+    indent <= 3
+        && (1..=6).contains(&level)
+        && rest[level..].chars().next().is_none_or(|c| c == ' ' || c == '\t')
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_tags_in_order() {
+        assert_eq!(
+            extract_tags("今日は #仕事 と #Work/Plan-, then #work/plan again #a_b/"),
+            vec!["仕事", "work/plan", "a_b"]
+        );
+        assert_eq!(extract_tags("#1 #2024 #v2 issue#3 (#x)"), vec!["v2"]);
+    }
+
+    #[test]
+    fn ignores_code_and_headings() {
+        let content = "# Title\n## Meeting #work\n   ### #deep\n####### #seven\n#tag-line\n`#code` #real\n```\n#fenced\n```\n~~~\n#tilde\n~~~\n#after";
+        assert_eq!(extract_tags(content), vec!["seven", "tag-line", "real", "after"]);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::storage::DiaryStorage;
+use chrono::NaiveDate;
+
+/// Markdown list markers that can start a task item.
+const LIST_MARKERS: [&str; 3] = ["- ", "* ", "+ "];
+
+/// An open checkbox somewhere in the diary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenTask {
+    pub date: NaiveDate,
+    /// Line of the entry the task is on, counting from 0.
+    pub line: usize,
+    /// The task text after `- [ ] `.
+    pub text: String,
+}
+
+/// The text after the checkbox when `line` is an open task.
+fn open_task_text(line: &str) -> Option<&str> {
+    let item = line.trim_start();
+    LIST_MARKERS
+        .iter()
+        .find_map(|marker| item.strip_prefix(marker)?.strip_prefix("[ ]"))
+        .map(str::trim)
+}
+
+/// The `- [ ] ...` lines of an entry, as written.
+pub fn unfinished(content: &str) -> impl Iterator<Item = &str> {
+    content.lines().filter(|line| open_task_text(line).is_some())
+}
+
+/// Every open task in the diary, newest entry first and in line order within an entry.
+pub fn open_tasks(storage: &DiaryStorage) -> Vec<OpenTask> {
+    storage
+        .entries_in_range(..)
+        .rev()
+        .flat_map(|(date, content)| {
+            content.lines().enumerate().filter_map(|(line, text)| {
+                open_task_text(text).map(|text| OpenTask {
+                    date: *date,
+                    line,
+                    text: text.to_string(),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Checks or unchecks the task on `line`; a line that is not a task becomes an open one.
+pub fn toggle(line: &str) -> String {
+    let item = line.trim_start();
+    let indent = &line[..line.len() - item.len()];
+
+    for marker in LIST_MARKERS {
+        let Some(rest) = item.strip_prefix(marker) else {
+            continue;
+        };
+        return if let Some(text) = rest.strip_prefix("[ ]") {
+            format!("{}{}[x]{}", indent, marker, text)
+        } else if let Some(text) = rest.strip_prefix("[x]").or_else(|| rest.strip_prefix("[X]")) {
+            format!("{}{}[ ]{}", indent, marker, text)
+        } else {
+            format!("{}{}[ ] {}", indent, marker, rest)
+        };
+    }
+    format!("{}- [ ] {}", indent, item)
+}
+
+/// Appends the open tasks of `previous` that `content` does not already list.
+pub fn carry_over(content: &str, previous: &str) -> String {
+    let existing: Vec<&str> = content.lines().filter_map(open_task_text).collect();
+    let carried: Vec<&str> = unfinished(previous)
+        .filter(|line| open_task_text(line).is_some_and(|text| !existing.contains(&text)))
+        .collect();
+    if carried.is_empty() {
+        return content.to_string();
+    }
+
+    let mut result = content.trim_end().to_string();
+    if !result.is_empty() {
+        result.push_str("\n\n");
+    }
+    result.push_str(&carried.join("\n"));
+    result.push('\n');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_open_checkboxes() {
+        let content = "# Plan\n- [ ] write report\n- [x] send mail\n  * [ ] nested\n- [] not a task\n+ [ ]";
+        assert_eq!(
+            unfinished(content).collect::<Vec<_>>(),
+            vec!["- [ ] write report", "  * [ ] nested", "+ [ ]"]
+        );
+
+        let mut storage = DiaryStorage::new();
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        storage.set_entry(date, String::from(content));
+        assert_eq!(
+            open_tasks(&storage)[1],
+            OpenTask { date, line: 3, text: String::from("nested") }
+        );
+    }
+
+    #[test]
+    fn toggles_and_converts_lines() {
+        assert_eq!(toggle("  - [ ] report"), "  - [x] report");
+        assert_eq!(toggle("* [X] report"), "* [ ] report");
+        assert_eq!(toggle("+ report"), "+ [ ] report");
+        assert_eq!(toggle("  report"), "  - [ ] report");
+        assert_eq!(toggle(""), "- [ ] ");
+    }
+
+    #[test]
+    fn carries_over_missing_tasks() {
+        let previous = "- [ ] report\n- [x] mail\n- [ ] call";
+        assert_eq!(carry_over("# Today\n- [ ] call\n", previous), "# Today\n- [ ] call\n\n- [ ] report\n");
+        assert_eq!(carry_over("", previous), "- [ ] report\n- [ ] call\n");
+        assert_eq!(carry_over("text", "- [x] done"), "text");
+    }
+}
@@ -0,0 +1,146 @@
+use crate::i18n::Locale;
+use crate::storage::DiaryStorage;
+use crate::tasks;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Entry templates, read from the `*.md` files of the templates folder and
+/// keyed by file name without the extension. `default` is used for new
+/// entries, unless there is one named after the weekday (`monday`, ...).
+#[derive(Debug, Default)]
+pub struct Templates {
+    templates: BTreeMap<String, String>,
+}
+
+impl Templates {
+    /// Loads every template in `dir`; a missing folder means no templates.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let mut templates = BTreeMap::new();
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                templates.insert(name.to_string(), fs::read_to_string(&path)?);
+            }
+        }
+        Ok(Self { templates })
+    }
+
+    /// Template names in alphabetical order, as listed in the picker.
+    pub fn names(&self) -> Vec<&str> {
+        self.templates.keys().map(String::as_str).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.templates.get(name).map(String::as_str)
+    }
+
+    /// The template for a new entry on `date`: its weekday's, or the default.
+    pub fn for_date(&self, date: NaiveDate) -> Option<&str> {
+        self.get(weekday_name(date.weekday())).or_else(|| self.get("default"))
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// Expands the `{{variable}}`s of a template for an entry on `date`:
+///
+/// - `{{date}}`: the date as `YYYY-MM-DD`
+/// - `{{weekday}}`: the weekday name in the UI language
+/// - `{{yesterday_unfinished_tasks}}`: the `- [ ]` lines of the previous day's entry
+/// - `{{cursor}}`: removed; the editor cursor starts there
+///
+/// Unknown variables are left as they are. Returns the text and the cursor
+/// position in characters, if the template has one.
+pub fn render(template: &str, date: NaiveDate, storage: &DiaryStorage, locale: Locale) -> (String, Option<usize>) {
+    let mut output = String::new();
+    let mut cursor = None;
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let variable = &rest[start..start + len + 2];
+        match variable[2..variable.len() - 2].trim() {
+            "date" => output.push_str(&date.format("%Y-%m-%d").to_string()),
+            "weekday" => output.push_str(locale.weekday_long(date.weekday())),
+            "yesterday_unfinished_tasks" => {
+                let yesterday = storage.get_entry(&(date - Duration::days(1))).unwrap_or_default();
+                output.push_str(&tasks::unfinished(&yesterday).collect::<Vec<_>>().join("\n"));
+            }
+            "cursor" if cursor.is_none() => cursor = Some(output.chars().count()),
+            "cursor" => {}
+            _ => output.push_str(variable),
+        }
+        rest = &rest[start + len + 2..];
+    }
+    output.push_str(rest);
+
+    (output, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_variables() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let mut storage = DiaryStorage::new();
+        storage.set_entry(
+            date - Duration::days(1),
+            String::from("- [x] done\n- [ ] carry over\n- [ ] this too"),
+        );
+
+        let (text, cursor) = render(
+            "# {{ date }} ({{weekday}})\n\n{{cursor}}\n\n## Todo\n{{yesterday_unfinished_tasks}}\n{{unknown}} {{",
+            date,
+            &storage,
+            Locale::En,
+        );
+
+        assert_eq!(
+            text,
+            "# 2024-03-15 (Friday)\n\n\n\n## Todo\n- [ ] carry over\n- [ ] this too\n{{unknown}} {{"
+        );
+        assert_eq!(cursor, Some(23));
+    }
+
+    #[test]
+    fn weekday_template_wins_over_default() {
+        let templates = Templates {
+            templates: BTreeMap::from([
+                (String::from("default"), String::from("plain")),
+                (String::from("friday"), String::from("weekly review")),
+            ]),
+        };
+        let friday = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+        assert_eq!(templates.for_date(friday), Some("weekly review"));
+        assert_eq!(templates.for_date(friday + Duration::days(1)), Some("plain"));
+    }
+}
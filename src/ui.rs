@@ -1,14 +1,23 @@
-use crate::app::{App, Mode};
-use chrono::{Datelike, Local};
+use crate::app::{App, Mode, PromptKind};
+use crate::era;
+use crate::fields::{FieldKind, FieldValue};
+use crate::holidays;
+use crate::mood;
+use crate::on_this_day::Ago;
+use crate::rokuyo;
+use crate::storage::Note;
+use crate::text;
+use chrono::{Datelike, Local, NaiveDate, Weekday};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    symbols,
+    widgets::{Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Wrap},
     Frame,
 };
 
-pub fn draw(f: &mut Frame, app: &App) {
+pub fn draw(f: &mut Frame, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -17,8 +26,50 @@ pub fn draw(f: &mut Frame, app: &App) {
         ])
         .split(f.area());
 
+    let show_panel = app.show_on_this_day || app.show_backlinks;
+    let (calendar_area, panel_area) = if app.mode == Mode::Calendar && show_panel {
+        // The calendar keeps at least one month's width, week numbers included.
+        let month_width = app.calendar.month_cell_width();
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(month_width), Constraint::Length(ON_THIS_DAY_WIDTH)])
+            .split(chunks[0]);
+        (split[0], Some(split[1]))
+    } else {
+        (chunks[0], None)
+    };
+
+    let (rows, cols) = app.calendar.grid_for_area(calendar_area.width, calendar_area.height);
+    app.calendar.set_grid(rows, cols);
+    app.editor.adjust_scroll(chunks[0].height.saturating_sub(2) as usize);
+
+    if let Some(panel_area) = panel_area {
+        match (app.show_on_this_day, app.show_backlinks) {
+            (true, true) => {
+                let split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(panel_area);
+                draw_on_this_day(f, app, split[0]);
+                draw_backlinks(f, app, split[1]);
+            }
+            (true, false) => draw_on_this_day(f, app, panel_area),
+            _ => draw_backlinks(f, app, panel_area),
+        }
+    }
+
     match app.mode {
-        Mode::Calendar => draw_calendar(f, app, chunks[0]),
+        Mode::Calendar => draw_calendar(f, app, calendar_area),
+        Mode::Week => draw_week(f, app, chunks[0]),
+        Mode::Agenda => draw_agenda(f, app, chunks[0]),
+        Mode::Stats => draw_stats(f, app, chunks[0]),
+        Mode::Tags => draw_tags(f, app, chunks[0]),
+        Mode::Templates => draw_templates(f, app, chunks[0]),
+        Mode::Tasks => draw_tasks(f, app, chunks[0]),
+        Mode::Form => {
+            draw_calendar(f, app, calendar_area);
+            draw_form(f, app, chunks[0]);
+        }
         Mode::Editor => draw_editor(f, app, chunks[0]),
     }
 
@@ -27,57 +78,53 @@ pub fn draw(f: &mut Frame, app: &App) {
 
 fn draw_calendar(f: &mut Frame, app: &App, area: Rect) {
     let months = app.calendar.get_display_months();
+    let field_range = app.field_overlay_range();
+
+    let grid_rows = app.calendar.grid_rows;
+    let grid_cols = app.calendar.grid_cols;
 
     let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-        ])
+        .constraints(vec![Constraint::Ratio(1, grid_rows as u32); grid_rows])
         .split(area);
 
     for (row_idx, row) in rows.iter().enumerate() {
         let cols = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(33),
-                Constraint::Percentage(33),
-                Constraint::Percentage(34),
-            ])
+            .constraints(vec![Constraint::Ratio(1, grid_cols as u32); grid_cols])
             .split(*row);
 
         for (col_idx, col) in cols.iter().enumerate() {
-            let month_idx = row_idx * 3 + col_idx;
+            let month_idx = row_idx * grid_cols + col_idx;
             if let Some((year, month)) = months.get(month_idx) {
-                draw_month(f, app, *col, *year, *month);
+                draw_month(f, app, *col, *year, *month, field_range);
             }
         }
     }
 }
 
-fn draw_month(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32) {
-    let month_name = match month {
-        1 => "1月",
-        2 => "2月",
-        3 => "3月",
-        4 => "4月",
-        5 => "5月",
-        6 => "6月",
-        7 => "7月",
-        8 => "8月",
-        9 => "9月",
-        10 => "10月",
-        11 => "11月",
-        12 => "12月",
-        _ => "?月",
-    };
-
-    let title = format!(" {}/{} ", year, month_name);
-    let block = Block::default()
+/// `field_range` scales the markers of the number field shown with `F`.
+fn draw_month(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32, field_range: Option<(f64, f64)>) {
+    let locale = app.locale;
+    let month_title = app
+        .config
+        .show_era
+        .then(|| era::format_year_month(year, month))
+        .flatten()
+        .unwrap_or_else(|| {
+            locale.format("calendar.month_title", &[&year.to_string(), locale.month_name(month)])
+        });
+    let title = format!(" {} ", month_title);
+    let mut block = Block::default()
         .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::White));
+    // Day cells are too narrow for rokuyō names, so the selected day's goes on the border.
+    let selected = app.calendar.selected_date;
+    if app.config.show_rokuyo && (selected.year(), selected.month()) == (year, month) {
+        let label = format!(" {} {} ", selected.format("%-m/%-d"), rokuyo::rokuyo(selected));
+        block = block.title_bottom(Line::from(label).right_aligned());
+    }
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -87,23 +134,38 @@ fn draw_month(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32) {
 
     let mut lines = vec![];
 
-    let header = Line::from(vec![
-        Span::styled("日 ", Style::default().fg(Color::Red)),
-        Span::styled("月 ", Style::default().fg(Color::White)),
-        Span::styled("火 ", Style::default().fg(Color::White)),
-        Span::styled("水 ", Style::default().fg(Color::White)),
-        Span::styled("木 ", Style::default().fg(Color::White)),
-        Span::styled("金 ", Style::default().fg(Color::White)),
-        Span::styled("土 ", Style::default().fg(Color::Blue)),
-    ]);
-    lines.push(header);
+    let mut header_spans = vec![];
+    if app.calendar.show_week_numbers {
+        header_spans.push(Span::styled(
+            format!("{} ", locale.text("calendar.week_column")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    for weekday in app.calendar.weekdays() {
+        header_spans.push(Span::styled(
+            format!("{} ", locale.weekday_short(weekday)),
+            Style::default().fg(weekday_color(weekday)),
+        ));
+    }
+    lines.push(Line::from(header_spans));
+
+    let padding = days.iter().take_while(|d| d.is_none()).count() as i64;
+    let grid_start = NaiveDate::from_ymd_opt(year, month, 1).unwrap() - chrono::Duration::days(padding);
 
     let mut week_spans = vec![];
     for (i, day_opt) in days.iter().enumerate() {
+        if i % 7 == 0 && app.calendar.show_week_numbers {
+            let row_start = grid_start + chrono::Duration::days(i as i64);
+            week_spans.push(Span::styled(
+                format!("{:2} ", app.calendar.week_number(row_start)),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
         let day_str = if let Some(date) = day_opt {
-            format!("{:2} ", date.day())
+            format!("{:2}", date.day())
         } else {
-            "   ".to_string()
+            "  ".to_string()
         };
 
         let is_selected = day_opt
@@ -118,14 +180,16 @@ fn draw_month(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32) {
             .map(|d| app.has_entry(d))
             .unwrap_or(false);
 
-        let weekday = i % 7;
-        let base_color = match weekday {
-            0 => Color::Red,
-            6 => Color::Blue,
-            _ => Color::White,
-        };
+        let base_color = day_opt
+            .as_ref()
+            .map(|d| day_color(*d))
+            .unwrap_or(Color::White);
 
-        let mut style = Style::default().fg(base_color);
+        let mood = day_opt
+            .as_ref()
+            .and_then(|d| app.storage.mood(d))
+            .filter(|_| app.config.mood_colors);
+        let mut style = Style::default().fg(mood.map(mood_color).unwrap_or(base_color));
 
         if is_selected {
             style = style.bg(Color::Cyan).fg(Color::Black).add_modifier(Modifier::BOLD);
@@ -133,11 +197,44 @@ fn draw_month(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32) {
             style = style.add_modifier(Modifier::BOLD).fg(Color::Yellow);
         }
 
+        if !is_selected && day_opt.as_ref().is_some_and(|d| app.matches_tag_filter(d)) {
+            style = style.bg(Color::Blue).fg(Color::White);
+        }
+
         if has_entry && !is_selected {
             style = style.add_modifier(Modifier::UNDERLINED);
+            if !is_today && day_opt.as_ref().is_some_and(|d| app.goal_met(d)) {
+                // Mood colors take precedence; goal days still stand out in bold.
+                if mood.is_none() {
+                    style = style.fg(Color::Green);
+                }
+                style = style.add_modifier(Modifier::BOLD);
+            }
         }
 
+        let has_overlay = day_opt
+            .as_ref()
+            .is_some_and(|d| app.overlay.contains_key(d));
+        // The field overlay takes the marker column while it is shown.
+        let (marker, marker_color) = match app.field_overlay_def() {
+            Some(def) => day_opt
+                .as_ref()
+                .and_then(|d| app.storage.metadata(d))
+                .and_then(|metadata| metadata.fields.get(&def.name))
+                .map(|value| field_marker(def.level(value, field_range), value))
+                .unwrap_or((" ", Color::Reset)),
+            None if has_overlay => ("•", Color::Magenta),
+            None if day_opt.as_ref().is_some_and(|d| app.links.is_linked(d)) => ("«", Color::LightBlue),
+            None => (" ", Color::Reset),
+        };
+        let marker_style = if is_selected {
+            Style::default().bg(Color::Cyan).fg(Color::Black)
+        } else {
+            Style::default().fg(marker_color)
+        };
+
         week_spans.push(Span::styled(day_str, style));
+        week_spans.push(Span::styled(marker, marker_style));
 
         if (i + 1) % 7 == 0 || i == days.len() - 1 {
             lines.push(Line::from(week_spans.clone()));
@@ -152,9 +249,754 @@ fn draw_month(f: &mut Frame, app: &App, area: Rect, year: i32, month: u32) {
     f.render_widget(calendar_widget, inner);
 }
 
+/// Bars from the lowest to the highest field level.
+const FIELD_LEVELS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+
+/// The calendar marker for a field value: a dot for bools, a bar otherwise.
+fn field_marker(level: Option<f64>, value: &FieldValue) -> (&'static str, Color) {
+    match value {
+        FieldValue::Bool(true) => ("●", Color::Green),
+        FieldValue::Bool(false) => ("○", Color::DarkGray),
+        _ => match level {
+            Some(level) => {
+                let index = (level.clamp(0.0, 1.0) * (FIELD_LEVELS.len() - 1) as f64).round() as usize;
+                (FIELD_LEVELS[index], Color::LightCyan)
+            }
+            None => ("?", Color::DarkGray),
+        },
+    }
+}
+
+/// Width of the "On this day" panel beside the calendar.
+const ON_THIS_DAY_WIDTH: u16 = 40;
+/// Lines of each entry shown in the "On this day" panel.
+const ON_THIS_DAY_SNIPPET_LINES: usize = 3;
+
+fn draw_on_this_day(f: &mut Frame, app: &App, area: Rect) {
+    let locale = app.locale;
+    let block = Block::default()
+        .title(format!(" {} ", locale.text("on_this_day.title")))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let memories = app.memories();
+    if memories.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            locale.text("on_this_day.empty"),
+            Style::default().fg(Color::DarkGray),
+        ))
+        .wrap(Wrap { trim: false });
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let mut lines = Vec::new();
+    for (i, memory) in memories.iter().enumerate() {
+        let ago = match memory.ago {
+            Ago::Years(1) => locale.text("on_this_day.year_ago").to_string(),
+            Ago::Years(n) => locale.format("on_this_day.years_ago", &[&n.to_string()]),
+            Ago::Month => locale.text("on_this_day.month_ago").to_string(),
+            Ago::Week => locale.text("on_this_day.week_ago").to_string(),
+        };
+        let key = if i < 9 { format!("{} ", i + 1) } else { String::from("  ") };
+
+        lines.push(Line::from(vec![
+            Span::styled(key, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!("{} ({}) ", memory.date.format("%Y-%m-%d"), locale.weekday_short(memory.date.weekday())),
+                Style::default().fg(day_color(memory.date)),
+            ),
+            Span::styled(ago, Style::default().fg(Color::DarkGray)),
+        ]));
+
+        let content = app.storage.get_entry(&memory.date).unwrap_or_default();
+        for line in content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(ON_THIS_DAY_SNIPPET_LINES)
+        {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", line.trim()),
+                Style::default().fg(Color::White),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_backlinks(f: &mut Frame, app: &App, area: Rect) {
+    let locale = app.locale;
+    let block = Block::default()
+        .title(format!(" {} ", locale.text("backlinks.title")))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let backlinks = app.backlinks();
+    if backlinks.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            locale.text("backlinks.empty"),
+            Style::default().fg(Color::DarkGray),
+        ))
+        .wrap(Wrap { trim: false });
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let mut lines = Vec::new();
+    for (date, mentions) in &backlinks {
+        lines.push(Line::from(Span::styled(
+            format!("{} ({})", date.format("%Y-%m-%d"), locale.weekday_short(date.weekday())),
+            Style::default().fg(day_color(*date)),
+        )));
+        for mention in mentions {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", mention.trim()),
+                Style::default().fg(Color::White),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Minimum width of a day column before the week view falls back to stacked rows.
+const WEEK_COLUMN_MIN_WIDTH: u16 = 14;
+
+fn weekday_color(weekday: Weekday) -> Color {
+    match weekday {
+        Weekday::Sun => Color::Red,
+        Weekday::Sat => Color::Blue,
+        _ => Color::White,
+    }
+}
+
+/// From red for the worst mood to green for the best.
+fn mood_color(mood: u8) -> Color {
+    match mood {
+        1 => Color::Red,
+        2 => Color::LightRed,
+        3 => Color::Yellow,
+        4 => Color::LightGreen,
+        _ => Color::Green,
+    }
+}
+
+/// Public holidays get the same coloring as Sundays.
+fn day_color(date: NaiveDate) -> Color {
+    if holidays::is_holiday(date) {
+        Color::Red
+    } else {
+        weekday_color(date.weekday())
+    }
+}
+
+fn draw_week(f: &mut Frame, app: &App, area: Rect) {
+    let days = app.calendar.get_week_days();
+    let today = Local::now().naive_local().date();
+
+    let direction = if area.width >= WEEK_COLUMN_MIN_WIDTH * 7 {
+        Direction::Horizontal
+    } else {
+        Direction::Vertical
+    };
+
+    let cells = Layout::default()
+        .direction(direction)
+        .constraints(vec![Constraint::Ratio(1, 7); 7])
+        .split(area);
+
+    for (date, cell) in days.iter().zip(cells.iter()) {
+        let mut title_style = Style::default().fg(day_color(*date));
+        if *date == today {
+            title_style = title_style.add_modifier(Modifier::BOLD).fg(Color::Yellow);
+        }
+
+        let border_style = if *date == app.calendar.selected_date {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let mut title = format!(
+            " {} ({}) ",
+            date.format("%m/%d"),
+            app.locale.weekday_short(date.weekday())
+        );
+        if app.config.show_rokuyo {
+            title.push_str(rokuyo::rokuyo(*date));
+            title.push(' ');
+        }
+        let block = Block::default()
+            .title(Span::styled(title, title_style))
+            .borders(Borders::ALL)
+            .border_style(border_style);
+
+        let inner = block.inner(*cell);
+        f.render_widget(block, *cell);
+
+        let mut lines: Vec<Line> = app
+            .storage
+            .get_entry(date)
+            .map(|content| content.lines().map(|line| Line::from(line.to_string())).collect())
+            .unwrap_or_default();
+        lines.extend(app.storage.notes(date).iter().map(note_line));
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "-",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        lines.truncate(inner.height as usize);
+
+        let preview = Paragraph::new(lines)
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(preview, inner);
+    }
+}
+
+/// A note as `HH:MM text` with the time highlighted.
+fn note_line(note: &Note) -> Line<'_> {
+    Line::from(vec![
+        Span::styled(format!("{} ", note.time.format("%H:%M")), Style::default().fg(Color::Cyan)),
+        Span::raw(note.text.as_str()),
+    ])
+}
+
+fn draw_agenda(f: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title(format!(
+            " {} ",
+            app.locale.format("agenda.title", &[&app.storage.written_count().to_string()])
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let selected = app.calendar.selected_date;
+    let height = inner.height as usize;
+
+    // Only the days around the selection are read, so the list stays
+    // cheap no matter how large the diary grows. Days with only notes are listed too.
+    let storage = &app.storage;
+    let mut rows: Vec<NaiveDate> = std::iter::successors(storage.prev_written_date(&selected), |date| {
+        storage.prev_written_date(date)
+    })
+    .take(height / 2)
+    .collect();
+    rows.reverse();
+    let remaining = height.saturating_sub(rows.len());
+    let first = Some(selected)
+        .filter(|date| storage.is_written(date))
+        .or_else(|| storage.next_written_date(&selected));
+    rows.extend(std::iter::successors(first, |date| storage.next_written_date(date)).take(remaining));
+
+    if rows.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            app.locale.text("agenda.empty"),
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|date| {
+            let notes = app.storage.notes(date).len();
+            let notes = if notes > 0 {
+                app.locale.format("agenda.notes", &[&notes.to_string()])
+            } else {
+                String::new()
+            };
+
+            let line = Line::from(vec![
+                Span::styled(
+                    format!("{} ", date.format("%Y-%m-%d")),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("({}) ", app.locale.weekday_short(date.weekday())),
+                    Style::default().fg(day_color(*date)),
+                ),
+                Span::styled(
+                    format!(
+                        "{:>6}{} ",
+                        text::word_count(&app.storage.get_entry(date).unwrap_or_default()),
+                        app.locale.text("agenda.words")
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(app.storage.day_title(date).to_string(), Style::default().fg(Color::White)),
+                Span::styled(notes, Style::default().fg(Color::Cyan)),
+            ]);
+
+            if *date == selected {
+                line.style(
+                    Style::default()
+                        .bg(Color::Cyan)
+                        .fg(Color::Black)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_tags(f: &mut Frame, app: &App, area: Rect) {
+    let tags = app.tag_list();
+    let block = Block::default()
+        .title(format!(
+            " {} ",
+            app.locale.format("tags.title", &[&tags.len().to_string()])
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if tags.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            app.locale.text("tags.empty"),
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    // Keep the selected row visible by starting the window just above it.
+    let height = inner.height as usize;
+    let start = app.tag_selection.saturating_sub(height.saturating_sub(1) / 2);
+    let start = start.min(tags.len().saturating_sub(height));
+
+    let lines: Vec<Line> = tags
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(height)
+        .map(|(i, (tag, count))| {
+            let line = Line::from(vec![
+                Span::styled(format!("{:>5} ", count), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("#{}", tag), Style::default().fg(Color::White)),
+            ]);
+            if i == app.tag_selection {
+                line.style(
+                    Style::default()
+                        .bg(Color::Cyan)
+                        .fg(Color::Black)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else if app.tag_filter.as_deref() == Some(*tag) {
+                line.style(Style::default().fg(Color::Blue))
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Width of the field form popup.
+const FORM_WIDTH: u16 = 44;
+
+fn draw_form(f: &mut Frame, app: &App, area: Rect) {
+    let Some(form) = &app.form else {
+        return;
+    };
+    let fields = &app.config.fields;
+    let name_width = fields.iter().map(|def| def.name.chars().count()).max().unwrap_or(0);
+
+    let width = FORM_WIDTH.min(area.width);
+    let height = (fields.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let title = app.locale.format(
+        "form.title",
+        &[&form.date.format("%Y-%m-%d").to_string()],
+    );
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let lines: Vec<Line> = fields
+        .iter()
+        .zip(&form.values)
+        .enumerate()
+        .map(|(i, (def, value))| {
+            let is_selected = i == form.selected;
+            let value_text = match (&def.kind, value) {
+                (FieldKind::Number { unit }, _) if is_selected => {
+                    format!("{}█{}", form.input, unit.as_deref().unwrap_or_default())
+                }
+                (FieldKind::Enum { .. }, Some(value)) if is_selected => format!("< {} >", def.display(value)),
+                (_, Some(value)) => def.display(value),
+                (_, None) => String::from("-"),
+            };
+            let name = format!(
+                "{}{:width$}  ",
+                if is_selected { "> " } else { "  " },
+                def.name,
+                width = name_width
+            );
+            let line = Line::from(vec![
+                Span::styled(name, Style::default().fg(Color::White)),
+                Span::styled(value_text, Style::default().fg(Color::Yellow)),
+            ]);
+            if is_selected {
+                line.style(Style::default().add_modifier(Modifier::BOLD))
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Clear, popup);
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn draw_tasks(f: &mut Frame, app: &App, area: Rect) {
+    let tasks = app.task_list();
+    let block = Block::default()
+        .title(format!(
+            " {} ",
+            app.locale.format("tasks.title", &[&tasks.len().to_string()])
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if tasks.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            app.locale.text("tasks.empty"),
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(empty, inner);
+        return;
+    }
+
+    let selected = app.task_selection.min(tasks.len() - 1);
+    let height = inner.height as usize;
+    let start = selected.saturating_sub(height.saturating_sub(1) / 2);
+    let start = start.min(tasks.len().saturating_sub(height));
+
+    let lines: Vec<Line> = tasks
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(height)
+        .map(|(i, task)| {
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" {}  ", task.date.format("%Y-%m-%d")),
+                    Style::default().fg(day_color(task.date)),
+                ),
+                Span::styled("☐ ", Style::default().fg(Color::Yellow)),
+                Span::styled(task.text.as_str(), Style::default().fg(Color::White)),
+            ]);
+            if i == selected {
+                line.style(
+                    Style::default()
+                        .bg(Color::Cyan)
+                        .fg(Color::Black)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_templates(f: &mut Frame, app: &App, area: Rect) {
+    let names = app.templates.names();
+    let date = app.calendar.selected_date.format("%Y-%m-%d").to_string();
+    let block = Block::default()
+        .title(format!(" {} ", app.locale.format("templates.title", &[&date])))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let name_width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+    let height = inner.height as usize;
+    let start = app.template_selection.saturating_sub(height.saturating_sub(1) / 2);
+    let start = start.min(names.len().saturating_sub(height));
+
+    let lines: Vec<Line> = names
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(height)
+        .map(|(i, name)| {
+            let preview = app.templates.get(name).map(text::title).unwrap_or_default();
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" {:width$}  ", name, width = name_width),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(preview.to_string(), Style::default().fg(Color::DarkGray)),
+            ]);
+            if i == app.template_selection {
+                line.style(
+                    Style::default()
+                        .bg(Color::Cyan)
+                        .fg(Color::Black)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_stats(f: &mut Frame, app: &App, area: Rect) {
+    let locale = app.locale;
+    let stats = &app.stats;
+    let today = Local::now().naive_local().date();
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let value_style = Style::default().fg(Color::White).add_modifier(Modifier::BOLD);
+    let days = |n: usize| locale.format("stats.days", &[&n.to_string()]);
+    let busiest = stats
+        .busiest_weekday()
+        .map(|(weekday, count)| {
+            locale.format(
+                "stats.busiest_value",
+                &[locale.weekday_long(weekday), &count.to_string()],
+            )
+        })
+        .unwrap_or_else(|| String::from("-"));
+
+    let mut rows = vec![
+        ("stats.entries", stats.total_entries().to_string()),
+        ("stats.total_words", stats.total_words().to_string()),
+        ("stats.average_words", format!("{:.1}", stats.average_words())),
+        ("stats.current_streak", days(stats.current_streak(today))),
+        ("stats.longest_streak", days(stats.longest_streak())),
+        ("stats.busiest_weekday", busiest),
+    ];
+    if let Some(average) = stats.average_mood() {
+        rows.push((
+            "stats.average_mood",
+            format!("{:.1} / {}", average, mood::MAX),
+        ));
+    }
+    if let Some(goal) = app.config.daily_word_goal.filter(|goal| *goal > 0) {
+        rows.push((
+            "stats.goal_days",
+            format!("{} ({})", days(stats.days_meeting_goal(goal)), goal),
+        ));
+    }
+
+    let has_moods = stats.average_mood().is_some();
+    let mut constraints = vec![
+        Constraint::Length(rows.len() as u16 + 2),
+        Constraint::Percentage(45),
+        Constraint::Min(8),
+    ];
+    if has_moods {
+        constraints.push(Constraint::Min(8));
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    let summary: Vec<Line> = rows
+        .into_iter()
+        .map(|(key, value)| {
+            Line::from(vec![
+                Span::styled(format!("{:<24}", locale.text(key)), label_style),
+                Span::styled(value, value_style),
+            ])
+        })
+        .collect();
+    f.render_widget(
+        Paragraph::new(summary).block(
+            Block::default()
+                .title(format!(" {} ", locale.text("stats.title")))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::White)),
+        ),
+        chunks[0],
+    );
+
+    let monthly = stats.monthly(today);
+
+    // Each bar takes three columns plus a one-column gap; show the latest months that fit.
+    let visible = (chunks[1].width.saturating_sub(1) as usize / 4).max(1);
+    let recent = &monthly[monthly.len().saturating_sub(visible)..];
+    let bars: Vec<Bar> = recent
+        .iter()
+        .map(|((_, month), month_stats)| {
+            Bar::default()
+                .value(month_stats.entries as u64)
+                .label(Line::from(format!("{:02}", month)))
+                .style(Style::default().fg(Color::Cyan))
+        })
+        .collect();
+    let range_title = match (recent.first(), recent.last()) {
+        (Some(((y1, m1), _)), Some(((y2, m2), _))) => {
+            format!(" {} {}/{:02} - {}/{:02} ", locale.text("stats.entries_per_month"), y1, m1, y2, m2)
+        }
+        _ => format!(" {} ", locale.text("stats.entries_per_month")),
+    };
+    f.render_widget(
+        BarChart::default()
+            .block(Block::default().title(range_title).borders(Borders::ALL))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(3)
+            .bar_gap(1)
+            .value_style(Style::default().fg(Color::Black).bg(Color::Cyan)),
+        chunks[1],
+    );
+
+    let points: Vec<(f64, f64)> = monthly
+        .iter()
+        .enumerate()
+        .map(|(i, (_, month_stats))| (i as f64, month_stats.words as f64))
+        .collect();
+    let max_words = monthly.iter().map(|(_, m)| m.words).max().unwrap_or(0).max(1);
+    let month_label = |index: usize| {
+        monthly
+            .get(index)
+            .map(|((year, month), _)| format!("{}/{:02}", year, month))
+            .unwrap_or_default()
+    };
+    let datasets = vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Green))
+        .data(&points)];
+    f.render_widget(
+        Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(format!(" {} ", locale.text("stats.words_per_month")))
+                    .borders(Borders::ALL),
+            )
+            .x_axis(
+                Axis::default()
+                    .bounds([0.0, points.len().saturating_sub(1).max(1) as f64])
+                    .labels([month_label(0), month_label(points.len().saturating_sub(1))])
+                    .style(label_style),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([0.0, max_words as f64])
+                    .labels([String::from("0"), max_words.to_string()])
+                    .style(label_style),
+            ),
+        chunks[2],
+    );
+    if !has_moods {
+        return;
+    }
+    let mood_points: Vec<(f64, f64)> = monthly
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ((year, month), _))| {
+            stats.month_mood(*year, *month).map(|average| (i as f64, average))
+        })
+        .collect();
+    let mood_datasets = vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Yellow))
+        .data(&mood_points)];
+    f.render_widget(
+        Chart::new(mood_datasets)
+            .block(
+                Block::default()
+                    .title(format!(" {} ", locale.text("stats.mood_per_month")))
+                    .borders(Borders::ALL),
+            )
+            .x_axis(
+                Axis::default()
+                    .bounds([0.0, points.len().saturating_sub(1).max(1) as f64])
+                    .labels([month_label(0), month_label(points.len().saturating_sub(1))])
+                    .style(label_style),
+            )
+            .y_axis(
+                Axis::default()
+                    .bounds([1.0, f64::from(mood::MAX)])
+                    .labels([String::from("1"), mood::MAX.to_string()])
+                    .style(label_style),
+            ),
+        chunks[3],
+    );
+}
+
 fn draw_editor(f: &mut Frame, app: &App, area: Rect) {
-    let date_str = app.calendar.selected_date.format("%Y-%m-%d (%A)").to_string();
-    let title = format!("Diary - {}", date_str);
+    let date = app.calendar.selected_date;
+    let date_str = app
+        .config
+        .show_era
+        .then(|| era::format_date(date))
+        .flatten()
+        .unwrap_or_else(|| date.format("%Y-%m-%d").to_string());
+    let mut title = app
+        .locale
+        .format("editor.title", &[&date_str, app.locale.weekday_long(date.weekday())]);
+    if app.config.show_rokuyo {
+        title.push(' ');
+        title.push_str(rokuyo::rokuyo(date));
+    }
+    if let Some(goal) = app.config.daily_word_goal.filter(|goal| *goal > 0) {
+        title.push(' ');
+        title.push_str(&goal_gauge(text::word_count(&app.editor.content), goal));
+    }
+
+    // The day's notes are shown read-only below the text.
+    let notes = app.storage.notes(&date);
+    let area = if notes.is_empty() {
+        area
+    } else {
+        let height = notes.len().min(EDITOR_NOTES_MAX_LINES) as u16 + 2;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(height)])
+            .split(area);
+        let notes_block = Block::default()
+            .title(format!(" {} ", app.locale.text("editor.notes")))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+        // The latest notes matter most when they do not all fit.
+        let skip = notes.len().saturating_sub(EDITOR_NOTES_MAX_LINES);
+        let lines: Vec<Line> = notes[skip..].iter().map(note_line).collect();
+        f.render_widget(Paragraph::new(lines).block(notes_block), chunks[1]);
+        chunks[0]
+    };
 
     let block = Block::default()
         .title(title)
@@ -178,12 +1020,101 @@ fn draw_editor(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(paragraph, inner);
 }
 
+/// Notes listed below the editor before older ones are left out.
+const EDITOR_NOTES_MAX_LINES: usize = 5;
+
+/// Cells in the editor title's word goal gauge.
+const GOAL_GAUGE_WIDTH: usize = 10;
+
+/// A text progress bar such as `[██████░░░░] 312/500`.
+fn goal_gauge(words: usize, goal: usize) -> String {
+    let filled = (words * GOAL_GAUGE_WIDTH / goal).min(GOAL_GAUGE_WIDTH);
+    format!(
+        "[{}{}] {}/{}{}",
+        "█".repeat(filled),
+        "░".repeat(GOAL_GAUGE_WIDTH - filled),
+        words,
+        goal,
+        if words >= goal { " ✓" } else { "" }
+    )
+}
+
 fn draw_status_bar(f: &mut Frame, app: &App, area: Rect) {
-    let status_text = Line::from(vec![
+    if let Some(prompt) = &app.prompt {
+        let label = match prompt.kind {
+            PromptKind::GoTo => app.locale.text("prompt.goto"),
+            PromptKind::Note => app.locale.text("prompt.note"),
+        };
+        let line = Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::Yellow)),
+            Span::styled(prompt.input.as_str(), Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(Color::White)),
+        ]);
+        f.render_widget(
+            Paragraph::new(line).style(Style::default().bg(Color::DarkGray)),
+            area,
+        );
+        return;
+    }
+
+    let mut spans = vec![
         Span::styled(&app.status_message, Style::default().fg(Color::Yellow)),
-    ]);
+    ];
+
+    if matches!(app.mode, Mode::Calendar | Mode::Week | Mode::Agenda) {
+        if let Some(tag) = &app.tag_filter {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(
+                format!("#{}", tag),
+                Style::default().fg(Color::White).bg(Color::Blue),
+            ));
+        }
+        if let Some(def) = app.field_overlay_def() {
+            let value = app
+                .storage
+                .metadata(&app.calendar.selected_date)
+                .and_then(|metadata| metadata.fields.get(&def.name))
+                .map(|value| def.display(value))
+                .unwrap_or_else(|| String::from("-"));
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(
+                format!("{}: {}", def.name, value),
+                Style::default().fg(Color::LightCyan),
+            ));
+        }
+        if let Some(mood) = app.storage.mood(&app.calendar.selected_date) {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(mood::emoji(mood), Style::default().fg(mood_color(mood))));
+        }
+        let streak = app.stats.current_streak(Local::now().naive_local().date());
+        if streak > 0 {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(
+                app.locale.format("status.streak", &[&streak.to_string()]),
+                Style::default().fg(Color::LightGreen),
+            ));
+        }
+        if app.config.show_rokuyo {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(
+                rokuyo::rokuyo(app.calendar.selected_date),
+                Style::default().fg(Color::White),
+            ));
+        }
+        if let Some(name) = holidays::holiday_name(app.calendar.selected_date) {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(
+                name,
+                Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some(events) = app.overlay.get(&app.calendar.selected_date) {
+            spans.push(Span::styled(" | ", Style::default().fg(Color::White)));
+            spans.push(Span::styled(events.join(", "), Style::default().fg(Color::Magenta)));
+        }
+    }
 
-    let status = Paragraph::new(status_text)
+    let status = Paragraph::new(Line::from(spans))
         .style(Style::default().bg(Color::DarkGray));
 
     f.render_widget(status, area);